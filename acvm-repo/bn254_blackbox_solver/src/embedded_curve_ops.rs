@@ -1,25 +1,18 @@
 use ark_ec::AffineRepr;
+use ark_ec::CurveGroup;
 use ark_ec::VariableBaseMSM;
-use ark_ff::{BigInt, MontConfig};
+use std::str::FromStr;
+
+use ark_ec::short_weierstrass::SWCurveConfig;
+use ark_ff::{AdditiveGroup, BigInt, BigInteger, Field, PrimeField, Zero};
 
 use crate::FieldElement;
 use acir::AcirField;
 use acir::BlackBoxFunc;
 
 use crate::BlackBoxResolutionError;
-
-/// Converts a field element to u128, returning an error if it doesn't fit.
-fn field_to_u128_limb(
-    limb: &FieldElement,
-    func: BlackBoxFunc,
-) -> Result<u128, BlackBoxResolutionError> {
-    limb.try_into_u128().ok_or_else(|| {
-        BlackBoxResolutionError::Failed(
-            func,
-            format!("Limb {} is not less than 2^128", limb.to_hex()),
-        )
-    })
-}
+use crate::generator;
+use crate::grumpkin_scalar::{ConstScalar, GrumpkinScalar, bn254_scalar_to_grumpkin_scalar};
 
 /// Performs multi scalar multiplication of points with scalars.
 pub fn multi_scalar_mul(
@@ -27,71 +20,498 @@ pub fn multi_scalar_mul(
     scalars_lo: &[FieldElement],
     scalars_hi: &[FieldElement],
 ) -> Result<(FieldElement, FieldElement, FieldElement), BlackBoxResolutionError> {
+    msm(points, scalars_lo, scalars_hi).map(|output_point| affine_to_triple(output_point.into()))
+}
+
+/// Performs multi scalar multiplication of points with scalars, returning the compressed encoding
+/// of the result instead of its coordinates.
+///
+/// The first byte is `0x02` if `y` is even and `0x03` if it's odd, followed by `x` in big-endian
+/// order. The point at infinity is encoded as 33 zero bytes.
+pub fn multi_scalar_mul_to_bytes(
+    points: &[FieldElement],
+    scalars_lo: &[FieldElement],
+    scalars_hi: &[FieldElement],
+) -> Result<[u8; 33], BlackBoxResolutionError> {
+    let output_point: ark_grumpkin::Affine = msm(points, scalars_lo, scalars_hi)?.into();
+
+    let mut bytes = [0; 33];
+    if let Some((x, y)) = output_point.xy() {
+        bytes[0] = if y.into_bigint().is_odd() { 0x03 } else { 0x02 };
+        bytes[1..].copy_from_slice(&FieldElement::from_repr(x).to_be_bytes());
+    }
+    Ok(bytes)
+}
+
+/// Same as [multi_scalar_mul], but collects the bases and scalars into the given buffers (which are
+/// cleared first) instead of allocating new ones, so that they can be reused across calls.
+pub fn multi_scalar_mul_into(
+    points: &[FieldElement],
+    scalars_lo: &[FieldElement],
+    scalars_hi: &[FieldElement],
+    bases_buf: &mut Vec<ark_grumpkin::Affine>,
+    ints_buf: &mut Vec<BigInt<4>>,
+) -> Result<(FieldElement, FieldElement, FieldElement), BlackBoxResolutionError> {
+    fill_msm_inputs(points, scalars_lo, scalars_hi, bases_buf, ints_buf)?;
+    let output_point = ark_grumpkin::Projective::msm_bigint(bases_buf, ints_buf);
+    Ok(affine_to_triple(output_point.into()))
+}
+
+/// Same as [multi_scalar_mul], but with the points given as separate arrays of `xs`, `ys` and
+/// `is_infinite` flags (`infs`) instead of as interleaved triples.
+pub fn multi_scalar_mul_soa(
+    xs: &[FieldElement],
+    ys: &[FieldElement],
+    infs: &[FieldElement],
+    scalars_lo: &[FieldElement],
+    scalars_hi: &[FieldElement],
+) -> Result<(FieldElement, FieldElement, FieldElement), BlackBoxResolutionError> {
+    let len = scalars_lo.len();
+    if xs.len() != len || ys.len() != len || infs.len() != len || scalars_hi.len() != len {
+        return Err(BlackBoxResolutionError::Failed(
+            BlackBoxFunc::MultiScalarMul,
+            "Points and scalars must have the same length".to_string(),
+        ));
+    }
+
+    let mut bases = Vec::with_capacity(len);
+    let mut big_ints = Vec::with_capacity(len);
+    for i in 0..len {
+        bases.push(validate_point([xs[i], ys[i], infs[i]], BlackBoxFunc::MultiScalarMul)?);
+        big_ints.push(msm_scalar(&scalars_lo[i], &scalars_hi[i])?);
+    }
+
+    let output_point = ark_grumpkin::Projective::msm_bigint(&bases, &big_ints);
+    Ok(affine_to_triple(output_point.into()))
+}
+
+/// Performs multi scalar multiplication of points with scalars, adding the `blinding` point to the result.
+///
+/// This can be used to mask intermediate points. The blinding point is validated like any other point.
+pub fn multi_scalar_mul_blinded(
+    points: &[FieldElement],
+    scalars_lo: &[FieldElement],
+    scalars_hi: &[FieldElement],
+    blinding: [FieldElement; 3],
+) -> Result<(FieldElement, FieldElement, FieldElement), BlackBoxResolutionError> {
+    msm_then_add(points, scalars_lo, scalars_hi, blinding)
+}
+
+/// Performs multi scalar multiplication of points with scalars and adds `addend` to the result.
+///
+/// This is equivalent to calling [embedded_curve_add] on the result of [multi_scalar_mul], but the
+/// addition happens before the result is converted to affine form, so it's normalized only once.
+pub fn msm_then_add(
+    points: &[FieldElement],
+    scalars_lo: &[FieldElement],
+    scalars_hi: &[FieldElement],
+    addend: [FieldElement; 3],
+) -> Result<(FieldElement, FieldElement, FieldElement), BlackBoxResolutionError> {
+    let addend = validate_point(addend, BlackBoxFunc::MultiScalarMul)?;
+    let output_point = msm(points, scalars_lo, scalars_hi)? + addend;
+    Ok(affine_to_triple(output_point.into()))
+}
+
+/// Computes the difference `MSM_a - MSM_b` of two multi scalar multiplications.
+///
+/// Both are computed in projective form and the result is normalized only once, so checking
+/// whether two MSMs are equal amounts to checking whether the result is the point at infinity.
+pub fn msm_sub(
+    points_a: &[FieldElement],
+    scalars_a_lo: &[FieldElement],
+    scalars_a_hi: &[FieldElement],
+    points_b: &[FieldElement],
+    scalars_b_lo: &[FieldElement],
+    scalars_b_hi: &[FieldElement],
+) -> Result<(FieldElement, FieldElement, FieldElement), BlackBoxResolutionError> {
+    let msm_a = msm(points_a, scalars_a_lo, scalars_a_hi)?;
+    let msm_b = msm(points_b, scalars_b_lo, scalars_b_hi)?;
+    Ok(affine_to_triple((msm_a - msm_b).into()))
+}
+
+/// Computes the residual `commitment - [value]G_index` of opening a vector commitment
+/// `sum_i [v_i]G_i` at `index`, where `generators` holds the `G_i` as `(x, y, is_infinite)` triples.
+///
+/// If `value` is the committed value at `index`, the residual is the commitment to the remaining values.
+pub fn open_commitment(
+    commitment: [FieldElement; 3],
+    index: usize,
+    value: FieldElement,
+    generators: &[FieldElement],
+) -> Result<[FieldElement; 3], BlackBoxResolutionError> {
+    let Some(generator) = generators.chunks_exact(3).nth(index) else {
+        return Err(BlackBoxResolutionError::Failed(
+            BlackBoxFunc::MultiScalarMul,
+            format!("Index {index} is out of bounds for {} generators", generators.len() / 3),
+        ));
+    };
+    let commitment = validate_point(commitment, BlackBoxFunc::MultiScalarMul)?;
+    let generator =
+        validate_point([generator[0], generator[1], generator[2]], BlackBoxFunc::MultiScalarMul)?;
+
+    let value = bn254_scalar_to_grumpkin_scalar(value).into_bigint();
+    let residual = commitment - generator.mul_bigint(value);
+    Ok(affine_to_point(residual.into()))
+}
+
+/// Computes the linear combination `sum_i [coeffs[i]] points[i]`, as used by interpolation gadgets.
+///
+/// This is a multi scalar multiplication where each coefficient is given as its `(lo, hi)` limbs.
+pub fn linear_combination(
+    points: &[[FieldElement; 3]],
+    coeffs: &[(FieldElement, FieldElement)],
+) -> Result<(FieldElement, FieldElement, FieldElement), BlackBoxResolutionError> {
+    if points.len() != coeffs.len() {
+        return Err(BlackBoxResolutionError::Failed(
+            BlackBoxFunc::MultiScalarMul,
+            "Points and coefficients must have the same length".to_string(),
+        ));
+    }
+
+    let mut bases = Vec::with_capacity(points.len());
+    let mut big_ints = Vec::with_capacity(coeffs.len());
+    for (point, (coeff_lo, coeff_hi)) in points.iter().zip(coeffs) {
+        bases.push(validate_point(*point, BlackBoxFunc::MultiScalarMul)?);
+        big_ints.push(msm_scalar(coeff_lo, coeff_hi)?);
+    }
+
+    let output_point = ark_grumpkin::Projective::msm_bigint(&bases, &big_ints);
+    Ok(affine_to_triple(output_point.into()))
+}
+
+/// Performs a multi scalar multiplication where only the scalars listed in `nonzero` are nonzero.
+///
+/// Each term is an `(index, lo, hi)` tuple giving the index of a point in `points` and the limbs
+/// of its scalar. Only the listed points are validated and multiplied, so committing to a sparse
+/// vector doesn't pay for the zero scalars.
+pub fn sparse_msm(
+    points: &[FieldElement],
+    nonzero: &[(usize, FieldElement, FieldElement)],
+) -> Result<[FieldElement; 3], BlackBoxResolutionError> {
+    let mut bases = Vec::with_capacity(nonzero.len());
+    let mut big_ints = Vec::with_capacity(nonzero.len());
+    for (index, scalar_lo, scalar_hi) in nonzero {
+        let Some(point) = points.chunks_exact(3).nth(*index) else {
+            return Err(BlackBoxResolutionError::Failed(
+                BlackBoxFunc::MultiScalarMul,
+                format!("Index {index} is out of bounds for {} points", points.len() / 3),
+            ));
+        };
+        bases.push(validate_point([point[0], point[1], point[2]], BlackBoxFunc::MultiScalarMul)?);
+        big_ints.push(msm_scalar(scalar_lo, scalar_hi)?);
+    }
+
+    let output_point = ark_grumpkin::Projective::msm_bigint(&bases, &big_ints);
+    Ok(affine_to_point(output_point.into()))
+}
+
+/// Computes `[a]P + [b]Q`, with the scalars given as their `(lo, hi)` limbs.
+///
+/// This uses Shamir's trick: both scalars are processed in a single double-and-add loop, adding
+/// `P`, `Q` or the precomputed `P + Q` depending on their bits, so only one chain of doublings is needed.
+pub fn double_scalar_mul(
+    p: [FieldElement; 3],
+    a: (FieldElement, FieldElement),
+    q: [FieldElement; 3],
+    b: (FieldElement, FieldElement),
+) -> Result<(FieldElement, FieldElement, FieldElement), BlackBoxResolutionError> {
+    let p: ark_grumpkin::Projective = validate_point(p, BlackBoxFunc::MultiScalarMul)?.into();
+    let q: ark_grumpkin::Projective = validate_point(q, BlackBoxFunc::MultiScalarMul)?.into();
+    let a = msm_scalar(&a.0, &a.1)?;
+    let b = msm_scalar(&b.0, &b.1)?;
+    let p_plus_q = p + q;
+
+    let mut output_point = ark_grumpkin::Projective::zero();
+    for i in (0..a.num_bits().max(b.num_bits()) as usize).rev() {
+        output_point.double_in_place();
+        match (a.get_bit(i), b.get_bit(i)) {
+            (true, true) => output_point += p_plus_q,
+            (true, false) => output_point += p,
+            (false, true) => output_point += q,
+            (false, false) => {}
+        }
+    }
+    Ok(affine_to_triple(output_point.into()))
+}
+
+/// Checks that the multi scalar multiplication of `points` with the given scalars equals `target`.
+pub fn verify_msm(
+    points: &[FieldElement],
+    scalars_lo: &[FieldElement],
+    scalars_hi: &[FieldElement],
+    target: [FieldElement; 3],
+) -> Result<bool, BlackBoxResolutionError> {
+    let target = validate_point(target, BlackBoxFunc::MultiScalarMul)?;
+    Ok(msm(points, scalars_lo, scalars_hi)?.into_affine() == target)
+}
+
+/// Checks whether the terms of a multi scalar multiplication sum to the point at infinity, so
+/// callers don't need to inspect the `is_infinite` flag of the [multi_scalar_mul] result.
+pub fn msm_terms_sum_to_zero(
+    points: &[FieldElement],
+    scalars_lo: &[FieldElement],
+    scalars_hi: &[FieldElement],
+) -> Result<bool, BlackBoxResolutionError> {
+    Ok(msm(points, scalars_lo, scalars_hi)?.is_zero())
+}
+
+fn msm(
+    points: &[FieldElement],
+    scalars_lo: &[FieldElement],
+    scalars_hi: &[FieldElement],
+) -> Result<ark_grumpkin::Projective, BlackBoxResolutionError> {
+    let (bases, big_ints) = msm_inputs(points, scalars_lo, scalars_hi)?;
+
+    // Perform batch multi-scalar multiplication
+    Ok(ark_grumpkin::Projective::msm_bigint(&bases, &big_ints))
+}
+
+fn check_msm_lengths(
+    points: &[FieldElement],
+    scalars_lo: &[FieldElement],
+    scalars_hi: &[FieldElement],
+) -> Result<(), BlackBoxResolutionError> {
     if points.len() != 3 * scalars_lo.len() || scalars_lo.len() != scalars_hi.len() {
         return Err(BlackBoxResolutionError::Failed(
             BlackBoxFunc::MultiScalarMul,
             "Points and scalars must have the same length".to_string(),
         ));
     }
+    Ok(())
+}
 
-    // Collect all bases (affine points) and scalars for batch MSM
+/// Validates the inputs of a multi scalar multiplication, returning its bases and scalars.
+fn msm_inputs(
+    points: &[FieldElement],
+    scalars_lo: &[FieldElement],
+    scalars_hi: &[FieldElement],
+) -> Result<(Vec<ark_grumpkin::Affine>, Vec<BigInt<4>>), BlackBoxResolutionError> {
     let mut bases = Vec::new();
     let mut big_ints = Vec::new();
+    fill_msm_inputs(points, scalars_lo, scalars_hi, &mut bases, &mut big_ints)?;
+    Ok((bases, big_ints))
+}
+
+/// Validates the inputs of a multi scalar multiplication, replacing the contents of `bases` and
+/// `big_ints` with its bases and scalars.
+fn fill_msm_inputs(
+    points: &[FieldElement],
+    scalars_lo: &[FieldElement],
+    scalars_hi: &[FieldElement],
+    bases: &mut Vec<ark_grumpkin::Affine>,
+    big_ints: &mut Vec<BigInt<4>>,
+) -> Result<(), BlackBoxResolutionError> {
+    check_msm_lengths(points, scalars_lo, scalars_hi)?;
+    validate_infinity_encoding(points)?;
 
+    bases.clear();
+    big_ints.clear();
+
+    // Collect all bases (affine points) and scalars for batch MSM
     for i in (0..points.len()).step_by(3) {
-        if points[i + 2] > FieldElement::one() {
-            return Err(BlackBoxResolutionError::Failed(
-                BlackBoxFunc::MultiScalarMul,
-                "EmbeddedCurvePoint is malformed (non-boolean `is_infinite` flag)".to_string(),
-            ));
+        let point = validate_point(
+            [points[i], points[i + 1], points[i + 2]],
+            BlackBoxFunc::MultiScalarMul,
+        )?;
+        let scalar_bigint = msm_scalar(&scalars_lo[i / 3], &scalars_hi[i / 3])?;
+
+        bases.push(point);
+        big_ints.push(scalar_bigint);
+    }
+    Ok(())
+}
+
+/// Debugging aid which computes a multi scalar multiplication with the bucket method, returning
+/// the sum of each window instead of the final point.
+///
+/// Scalars are split into `window_bits`-bit digits. The sum of window `w` is `sum_j j * B_j`, where
+/// `B_j` is the sum of the points whose `w`-th digit is `j`, so the MSM result is
+/// `sum_w 2^(w * window_bits) * S_w`. Comparing these against the bucket sums of another prover
+/// helps finding where two MSM implementations diverge.
+///
+/// This is only available with the `debug-tools` feature.
+#[cfg(feature = "debug-tools")]
+pub fn msm_window_sums(
+    points: &[FieldElement],
+    scalars_lo: &[FieldElement],
+    scalars_hi: &[FieldElement],
+    window_bits: usize,
+) -> Result<Vec<[FieldElement; 3]>, BlackBoxResolutionError> {
+    if !(1..=16).contains(&window_bits) {
+        return Err(BlackBoxResolutionError::Failed(
+            BlackBoxFunc::MultiScalarMul,
+            format!("Window size must be between 1 and 16 bits, got {window_bits}"),
+        ));
+    }
+
+    let (bases, big_ints) = msm_inputs(points, scalars_lo, scalars_hi)?;
+
+    let scalar_bits = ark_grumpkin::Fr::MODULUS_BIT_SIZE as usize;
+    let num_windows = scalar_bits.div_ceil(window_bits);
+    let mut window_sums = Vec::with_capacity(num_windows);
+    for window in 0..num_windows {
+        let mut buckets = vec![ark_grumpkin::Projective::zero(); (1 << window_bits) - 1];
+        for (base, scalar) in bases.iter().zip(&big_ints) {
+            let first_bit = window * window_bits;
+            let digit = (0..window_bits)
+                .filter(|bit| scalar.get_bit(first_bit + bit))
+                .fold(0, |digit, bit| digit | (1 << bit));
+            if digit != 0 {
+                buckets[digit - 1] += base;
+            }
+        }
+
+        // Running sum trick: the `j`-th bucket ends up being added `j` times.
+        let mut running_sum = ark_grumpkin::Projective::zero();
+        let mut window_sum = ark_grumpkin::Projective::zero();
+        for bucket in buckets.into_iter().rev() {
+            running_sum += bucket;
+            window_sum += running_sum;
         }
-        let point = create_point(points[i], points[i + 1], points[i + 2])
-            .map_err(|e| BlackBoxResolutionError::Failed(BlackBoxFunc::MultiScalarMul, e))?;
+        window_sums.push(window_sum);
+    }
 
-        let scalar_low: u128 =
-            field_to_u128_limb(&scalars_lo[i / 3], BlackBoxFunc::MultiScalarMul)?;
+    let window_sums = ark_grumpkin::Projective::normalize_batch(&window_sums);
+    Ok(window_sums.into_iter().map(affine_to_point).collect())
+}
 
-        let scalar_high: u128 =
-            field_to_u128_limb(&scalars_hi[i / 3], BlackBoxFunc::MultiScalarMul)?;
+/// Checks that every point flagged as infinite in `points` is encoded as `(0, 0, 1)`.
+///
+/// [multi_scalar_mul] runs this on its points before solving, so that a point with `is_infinite = 1`
+/// but non-zero coordinates is rejected instead of being treated as the identity.
+pub fn validate_infinity_encoding(points: &[FieldElement]) -> Result<(), BlackBoxResolutionError> {
+    if points.len() % 3 != 0 {
+        return Err(BlackBoxResolutionError::Failed(
+            BlackBoxFunc::MultiScalarMul,
+            "Points must be a sequence of (x, y, is_infinite) triples".to_string(),
+        ));
+    }
 
-        // Convert to BigInt<4>, using u64 limbs.
-        let limbs_array = [
-            scalar_low as u64,
-            (scalar_low >> 64) as u64,
-            scalar_high as u64,
-            (scalar_high >> 64) as u64,
-        ];
-        let scalar_bigint = BigInt::new(limbs_array);
-
-        // Check if this is smaller than the grumpkin modulus
-        if scalar_bigint >= ark_grumpkin::FrConfig::MODULUS {
-            // Format as hex string (big-endian, most significant limb first)
-            let hex_str = format!(
-                "{:016x}{:016x}{:016x}{:016x}",
-                limbs_array[3], limbs_array[2], limbs_array[1], limbs_array[0]
-            );
+    for (index, point) in points.chunks_exact(3).enumerate() {
+        if point[2].is_one() && !(point[0].is_zero() && point[1].is_zero()) {
             return Err(BlackBoxResolutionError::Failed(
                 BlackBoxFunc::MultiScalarMul,
-                format!("{hex_str} is not a valid grumpkin scalar"),
+                format!(
+                    "Point at index {index} is flagged as infinite but has non-zero coordinates ({}, {})",
+                    point[0].to_hex(),
+                    point[1].to_hex()
+                ),
             ));
         }
+    }
+    Ok(())
+}
 
-        bases.push(point);
-        big_ints.push(scalar_bigint);
+/// Performs multi scalar multiplication of a fixed number of points with scalars.
+///
+/// This validates its inputs exactly like [multi_scalar_mul], but doesn't allocate,
+/// which makes it a better fit for the very common one and two term cases.
+pub fn multi_scalar_mul_fixed<const N: usize>(
+    points: [[FieldElement; 3]; N],
+    scalars: [(FieldElement, FieldElement); N],
+) -> Result<(FieldElement, FieldElement, FieldElement), BlackBoxResolutionError> {
+    let mut output_point = ark_grumpkin::Projective::zero();
+    for (point, (scalar_lo, scalar_hi)) in points.into_iter().zip(scalars) {
+        let point = validate_point(point, BlackBoxFunc::MultiScalarMul)?;
+        let scalar_bigint = msm_scalar(&scalar_lo, &scalar_hi)?;
+        output_point += point.mul_bigint(scalar_bigint);
     }
+    Ok(affine_to_triple(output_point.into()))
+}
 
-    // Perform batch multi-scalar multiplication
-    let output_point = ark_grumpkin::Projective::msm_bigint(&bases, &big_ints);
-    let output_point = ark_grumpkin::Affine::from(output_point);
-
-    if let Some((out_x, out_y)) = output_point.xy() {
-        Ok((
-            FieldElement::from_repr(out_x),
-            FieldElement::from_repr(out_y),
-            FieldElement::from(u128::from(output_point.is_zero())),
-        ))
+/// Performs multi scalar multiplication of points with scalars, `chunk_size` terms at a time.
+///
+/// The result is the same as [multi_scalar_mul], but only one chunk of bases and scalars is held
+/// in memory at once, which suits memory-bounded environments.
+pub fn multi_scalar_mul_chunked(
+    points: &[FieldElement],
+    scalars_lo: &[FieldElement],
+    scalars_hi: &[FieldElement],
+    chunk_size: usize,
+) -> Result<(FieldElement, FieldElement, FieldElement), BlackBoxResolutionError> {
+    if chunk_size == 0 {
+        return Err(BlackBoxResolutionError::Failed(
+            BlackBoxFunc::MultiScalarMul,
+            "Chunk size must be greater than zero".to_string(),
+        ));
+    }
+    check_msm_lengths(points, scalars_lo, scalars_hi)?;
+
+    let mut output_point = ark_grumpkin::Projective::zero();
+    let chunks = points
+        .chunks(3 * chunk_size)
+        .zip(scalars_lo.chunks(chunk_size))
+        .zip(scalars_hi.chunks(chunk_size));
+    for ((points, scalars_lo), scalars_hi) in chunks {
+        output_point += msm(points, scalars_lo, scalars_hi)?;
+    }
+    Ok(affine_to_triple(output_point.into()))
+}
+
+/// Performs multi scalar multiplication of points with scalars that are all known constants.
+///
+/// This computes the same result as [multi_scalar_mul], but as the scalars were already validated
+/// it skips their range checks, drops the terms multiplied by zero and adds the terms multiplied
+/// by one directly instead of going through the MSM.
+pub fn multi_scalar_mul_const(
+    points: &[FieldElement],
+    scalars: &[ConstScalar],
+) -> Result<(FieldElement, FieldElement, FieldElement), BlackBoxResolutionError> {
+    if points.len() != 3 * scalars.len() {
+        return Err(BlackBoxResolutionError::Failed(
+            BlackBoxFunc::MultiScalarMul,
+            "Points and scalars must have the same length".to_string(),
+        ));
+    }
+
+    let mut bases = Vec::new();
+    let mut big_ints = Vec::new();
+    let mut output_point = ark_grumpkin::Projective::zero();
+
+    for (point, ConstScalar(scalar)) in points.chunks_exact(3).zip(scalars) {
+        let point = validate_point([point[0], point[1], point[2]], BlackBoxFunc::MultiScalarMul)?;
+        if scalar.is_zero() {
+            continue;
+        } else if scalar.is_one() {
+            output_point += point;
+        } else {
+            bases.push(point);
+            big_ints.push(scalar.into_bigint());
+        }
+    }
+
+    output_point += ark_grumpkin::Projective::msm_bigint(&bases, &big_ints);
+    Ok(affine_to_triple(output_point.into()))
+}
+
+/// Validates a `[x, y, is_infinite]` input point, reporting errors as coming from `func`.
+fn validate_point(
+    point: [FieldElement; 3],
+    func: BlackBoxFunc,
+) -> Result<ark_grumpkin::Affine, BlackBoxResolutionError> {
+    if point[2] > FieldElement::one() {
+        return Err(BlackBoxResolutionError::Failed(
+            func,
+            "EmbeddedCurvePoint is malformed (non-boolean `is_infinite` flag)".to_string(),
+        ));
+    }
+    create_point(point[0], point[1], point[2]).map_err(|e| BlackBoxResolutionError::Failed(func, e))
+}
+
+/// Combines the 128-bit limbs of a scalar, checking that it is a valid grumpkin scalar.
+fn msm_scalar(
+    scalar_lo: &FieldElement,
+    scalar_hi: &FieldElement,
+) -> Result<BigInt<4>, BlackBoxResolutionError> {
+    GrumpkinScalar::try_from((*scalar_lo, *scalar_hi)).map(GrumpkinScalar::into_bigint)
+}
+
+/// Converts an affine point into the `(x, y, is_infinite)` triple returned by the embedded curve functions.
+fn affine_to_triple(point: ark_grumpkin::Affine) -> (FieldElement, FieldElement, FieldElement) {
+    if let Some((x, y)) = point.xy() {
+        (FieldElement::from_repr(x), FieldElement::from_repr(y), FieldElement::zero())
     } else {
-        Ok((FieldElement::from(0_u128), FieldElement::from(0_u128), FieldElement::from(1_u128)))
+        (FieldElement::zero(), FieldElement::zero(), FieldElement::one())
     }
 }
 
@@ -120,72 +540,587 @@ pub fn embedded_curve_add(
         }
     }
 
-    let res = ark_grumpkin::Affine::from(point1 + point2);
-    if let Some((res_x, res_y)) = res.xy() {
-        Ok((FieldElement::from_repr(res_x), FieldElement::from_repr(res_y), FieldElement::zero()))
-    } else {
-        assert!(res.is_zero());
-        Ok((FieldElement::from(0_u128), FieldElement::from(0_u128), FieldElement::from(1_u128)))
-    }
+    Ok(affine_to_triple((point1 + point2).into()))
 }
 
-fn create_point(
-    x: FieldElement,
-    y: FieldElement,
-    is_infinite: FieldElement,
-) -> Result<ark_grumpkin::Affine, String> {
-    if is_infinite.is_one() {
-        return Ok(ark_grumpkin::Affine::zero());
-    } else if !is_infinite.is_zero() {
-        return Err("`is_infinite` flag is non-boolean".to_string());
+/// Returns whether the arrays of `(x, y, is_infinite)` triples `a` and `b` hold the same points,
+/// comparing each pair of points after validating them (so that all encodings of the point at
+/// infinity are equal). Returns `false` as soon as a pair differs, without validating the rest.
+pub fn point_arrays_equal(
+    a: &[FieldElement],
+    b: &[FieldElement],
+) -> Result<bool, BlackBoxResolutionError> {
+    if a.len() != b.len() || a.len() % 3 != 0 {
+        return Err(BlackBoxResolutionError::Failed(
+            BlackBoxFunc::EmbeddedCurveAdd,
+            "Point arrays must have the same length and consist of (x, y, is_infinite) triples"
+                .to_string(),
+        ));
     }
 
-    let point = ark_grumpkin::Affine::new_unchecked(x.into_repr(), y.into_repr());
-    if !point.is_on_curve() {
-        return Err(format!("Point ({}, {}) is not on curve", x.to_hex(), y.to_hex()));
-    };
-    if !point.is_in_correct_subgroup_assuming_on_curve() {
-        return Err(format!("Point ({}, {}) is not in correct subgroup", x.to_hex(), y.to_hex()));
-    };
-    Ok(point)
+    for (point_a, point_b) in a.chunks_exact(3).zip(b.chunks_exact(3)) {
+        let point_a =
+            validate_point([point_a[0], point_a[1], point_a[2]], BlackBoxFunc::EmbeddedCurveAdd)?;
+        let point_b =
+            validate_point([point_b[0], point_b[1], point_b[2]], BlackBoxFunc::EmbeddedCurveAdd)?;
+        if point_a != point_b {
+            return Ok(false);
+        }
+    }
+    Ok(true)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use ark_ff::BigInteger;
-    use num_bigint::BigUint;
-
-    fn get_generator() -> [FieldElement; 3] {
-        let generator = ark_grumpkin::Affine::generator();
-        let generator_x = FieldElement::from_repr(generator.x().unwrap());
-        let generator_y = FieldElement::from_repr(generator.y().unwrap());
-        [generator_x, generator_y, FieldElement::zero()]
+/// Negates each of the `(x, y, is_infinite)` triples in `points`, returning the negated triples
+/// (the point at infinity negates to itself, in its canonical encoding).
+pub fn negate_batch(points: &[FieldElement]) -> Result<Vec<FieldElement>, BlackBoxResolutionError> {
+    if points.len() % 3 != 0 {
+        return Err(BlackBoxResolutionError::Failed(
+            BlackBoxFunc::EmbeddedCurveAdd,
+            "Points must be a sequence of (x, y, is_infinite) triples".to_string(),
+        ));
     }
 
-    #[test]
-    fn smoke_test() -> Result<(), BlackBoxResolutionError> {
-        // We check that multiplying 1 by generator results in the generator
-        let generator = get_generator();
+    let mut negated = Vec::with_capacity(points.len());
+    for point in points.chunks_exact(3) {
+        let point = validate_point([point[0], point[1], point[2]], BlackBoxFunc::EmbeddedCurveAdd)?;
+        negated.extend(affine_to_point(-point));
+    }
+    Ok(negated)
+}
 
-        let res = multi_scalar_mul(&generator, &[FieldElement::one()], &[FieldElement::zero()])?;
+/// Validates `points` (a sequence of `(x, y, is_infinite)` triples) and returns them rotated left by
+/// `shift` points, so that the point at index `shift` comes first. Shifts wrap around.
+pub fn rotate_multiples(
+    points: &[FieldElement],
+    shift: usize,
+) -> Result<Vec<FieldElement>, BlackBoxResolutionError> {
+    if points.len() % 3 != 0 {
+        return Err(BlackBoxResolutionError::Failed(
+            BlackBoxFunc::MultiScalarMul,
+            "Points must be a sequence of (x, y, is_infinite) triples".to_string(),
+        ));
+    }
 
-        assert_eq!(generator[0], res.0);
-        assert_eq!(generator[1], res.1);
-        Ok(())
+    let mut rotated = Vec::with_capacity(points.len());
+    for point in points.chunks_exact(3) {
+        let point = validate_point([point[0], point[1], point[2]], BlackBoxFunc::MultiScalarMul)?;
+        rotated.extend(affine_to_point(point));
+    }
+    let num_points = points.len() / 3;
+    if num_points > 0 {
+        rotated.rotate_left(shift % num_points * 3);
     }
+    Ok(rotated)
+}
 
-    #[test]
-    fn low_high_smoke_test() -> Result<(), BlackBoxResolutionError> {
-        let points = get_generator();
-        let scalars_lo = [FieldElement::one()];
-        let scalars_hi = [FieldElement::from(2u128)];
+/// Size of the encoding of a point used by [points_to_bytes] and [points_from_bytes].
+const POINT_BYTES: usize = 64;
 
-        let res = multi_scalar_mul(&points, &scalars_lo, &scalars_hi)?;
-        let x = "0702ab9c7038eeecc179b4f209991bcb68c7cb05bf4c532d804ccac36199c9a9";
-        let y = "23f10e9e43a3ae8d75d24154e796aae12ae7af546716e8f81a2564f1b5814130";
+/// Validates `points` (a sequence of `(x, y, is_infinite)` triples) and serializes them into a single
+/// buffer, for example to hash them into a transcript.
+///
+/// Each point takes 64 bytes: its `x` and `y` coordinates in big-endian order. The point at infinity
+/// is encoded as 64 zero bytes, which is unambiguous because `(0, 0)` isn't on the curve.
+pub fn points_to_bytes(points: &[FieldElement]) -> Result<Vec<u8>, BlackBoxResolutionError> {
+    if points.len() % 3 != 0 {
+        return Err(BlackBoxResolutionError::Failed(
+            BlackBoxFunc::MultiScalarMul,
+            "Points must be a sequence of (x, y, is_infinite) triples".to_string(),
+        ));
+    }
 
-        assert_eq!(x, res.0.to_hex());
+    let mut bytes = Vec::with_capacity(points.len() / 3 * POINT_BYTES);
+    for point in points.chunks_exact(3) {
+        let point = validate_point([point[0], point[1], point[2]], BlackBoxFunc::MultiScalarMul)?;
+        let (x, y) = point.xy().unwrap_or_default();
+        bytes.extend(FieldElement::from_repr(x).to_be_bytes());
+        bytes.extend(FieldElement::from_repr(y).to_be_bytes());
+    }
+    Ok(bytes)
+}
+
+/// Parses points serialized with [points_to_bytes] back into `(x, y, is_infinite)` triples,
+/// checking that every coordinate is reduced and every point is valid.
+pub fn points_from_bytes(bytes: &[u8]) -> Result<Vec<FieldElement>, BlackBoxResolutionError> {
+    if bytes.len() % POINT_BYTES != 0 {
+        return Err(BlackBoxResolutionError::Failed(
+            BlackBoxFunc::MultiScalarMul,
+            format!("Serialized points must be a multiple of {POINT_BYTES} bytes long"),
+        ));
+    }
+
+    let mut points = Vec::with_capacity(bytes.len() / POINT_BYTES * 3);
+    for point_bytes in bytes.chunks_exact(POINT_BYTES) {
+        if point_bytes.iter().all(|byte| *byte == 0) {
+            points.extend(canonical_infinity());
+            continue;
+        }
+
+        let (x_bytes, y_bytes) = point_bytes.split_at(POINT_BYTES / 2);
+        let point =
+            [field_from_be_bytes(x_bytes)?, field_from_be_bytes(y_bytes)?, FieldElement::zero()];
+        points.extend(affine_to_point(validate_point(point, BlackBoxFunc::MultiScalarMul)?));
+    }
+    Ok(points)
+}
+
+/// Checks a batch of claimed point additions `a + b = c`, returning whether each claim is correct.
+///
+/// Each sum is recomputed with [embedded_curve_add], so invalid `a` and `b` inputs are errors,
+/// while a claimed result that isn't a valid point just makes its claim incorrect.
+pub fn verify_adds(
+    triples: &[([FieldElement; 3], [FieldElement; 3], [FieldElement; 3])],
+) -> Result<Vec<bool>, BlackBoxResolutionError> {
+    triples
+        .iter()
+        .map(|(a, b, claimed)| {
+            let (x, y, is_infinite) = embedded_curve_add(*a, *b)?;
+            Ok(normalize_point(*claimed).is_ok_and(|claimed| claimed == [x, y, is_infinite]))
+        })
+        .collect()
+}
+
+/// Validates `point` and adds it to `acc`, staying in projective form.
+///
+/// This is cheaper than chaining [embedded_curve_add] calls, which convert every sum back to affine.
+pub fn accumulate(
+    acc: &mut ark_grumpkin::Projective,
+    point: [FieldElement; 3],
+) -> Result<(), BlackBoxResolutionError> {
+    *acc += validate_point(point, BlackBoxFunc::EmbeddedCurveAdd)?;
+    Ok(())
+}
+
+/// Computes a multi scalar multiplication one term at a time, so that a verifier checking it
+/// incrementally can compare each partial sum against the one it expects.
+#[derive(Default)]
+pub struct MsmPartialVerifier {
+    sum: ark_grumpkin::Projective,
+}
+
+impl MsmPartialVerifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validates `point` and the `(lo, hi)` limbs of a scalar, adds `[scalar]point` to the sum and
+    /// returns the new partial sum.
+    pub fn add_term(
+        &mut self,
+        point: [FieldElement; 3],
+        scalar_lo: FieldElement,
+        scalar_hi: FieldElement,
+    ) -> Result<(FieldElement, FieldElement, FieldElement), BlackBoxResolutionError> {
+        let point = validate_point(point, BlackBoxFunc::MultiScalarMul)?;
+        let scalar = msm_scalar(&scalar_lo, &scalar_hi)?;
+        self.sum += point.mul_bigint(scalar);
+        Ok(self.partial_sum())
+    }
+
+    /// Returns the sum of the terms added so far (the point at infinity if there are none).
+    pub fn partial_sum(&self) -> (FieldElement, FieldElement, FieldElement) {
+        affine_to_triple(self.sum.into())
+    }
+}
+
+/// Computes `[1]P, [2]P, ..., [n]P` by repeatedly adding `point`, which is cheaper
+/// than `n` independent scalar multiplications. This is useful to build lookup tables.
+pub fn small_multiples(
+    point: [FieldElement; 3],
+    n: usize,
+) -> Result<Vec<(FieldElement, FieldElement, FieldElement)>, BlackBoxResolutionError> {
+    let point = validate_point(point, BlackBoxFunc::EmbeddedCurveAdd)?;
+
+    let mut multiples = Vec::with_capacity(n);
+    let mut multiple = ark_grumpkin::Projective::zero();
+    for _ in 0..n {
+        multiple += point;
+        multiples.push(multiple);
+    }
+
+    let multiples = ark_grumpkin::Projective::normalize_batch(&multiples);
+    Ok(multiples.into_iter().map(affine_to_triple).collect())
+}
+
+/// Computes `[s_i]P_i` for each `(P_i, (lo_i, hi_i))` pair, returning every product separately
+/// instead of their sum like [multi_scalar_mul] does.
+///
+/// The products are normalized to affine form together, which needs a single field inversion.
+pub fn batch_scalar_mul(
+    pairs: &[([FieldElement; 3], (FieldElement, FieldElement))],
+) -> Result<Vec<[FieldElement; 3]>, BlackBoxResolutionError> {
+    let mut products = Vec::with_capacity(pairs.len());
+    for (point, (scalar_lo, scalar_hi)) in pairs {
+        let point = validate_point(*point, BlackBoxFunc::MultiScalarMul)?;
+        let scalar = msm_scalar(scalar_lo, scalar_hi)?;
+        products.push(point.mul_bigint(scalar));
+    }
+
+    let products = ark_grumpkin::Projective::normalize_batch(&products);
+    Ok(products.into_iter().map(affine_to_point).collect())
+}
+
+/// Computes `[2^k]P` with `k` doublings, which is cheaper than a scalar multiplication by `2^k`.
+pub fn double_repeated(
+    point: [FieldElement; 3],
+    k: u32,
+) -> Result<(FieldElement, FieldElement, FieldElement), BlackBoxResolutionError> {
+    let mut output_point: ark_grumpkin::Projective =
+        validate_point(point, BlackBoxFunc::EmbeddedCurveAdd)?.into();
+    for _ in 0..k {
+        output_point.double_in_place();
+    }
+    Ok(affine_to_triple(output_point.into()))
+}
+
+/// Returns `a` if `cond` is true and `b` otherwise, after validating both points (the point at
+/// infinity is returned in its canonical encoding).
+///
+/// The selection is computed arithmetically as `b + cond * (a - b)` on each coordinate instead of
+/// branching on `cond`, mirroring how a circuit selects between two values.
+pub fn conditional_select(
+    cond: bool,
+    a: [FieldElement; 3],
+    b: [FieldElement; 3],
+) -> Result<[FieldElement; 3], BlackBoxResolutionError> {
+    let a = affine_to_point(validate_point(a, BlackBoxFunc::EmbeddedCurveAdd)?);
+    let b = affine_to_point(validate_point(b, BlackBoxFunc::EmbeddedCurveAdd)?);
+
+    let cond = FieldElement::from(cond);
+    Ok(std::array::from_fn(|i| b[i] + cond * (a[i] - b[i])))
+}
+
+/// Debugging aid which finds the `s` in `[0, max]` such that `[s]G == point`, where `G` is the
+/// generator, by adding `G` until reaching `point`. Returns `None` if there's no such `s` (or if
+/// `point` isn't a valid point).
+///
+/// This takes time linear in `max`, so it's only meant for checking small results in tests.
+pub fn small_discrete_log(point: [FieldElement; 3], max: u64) -> Option<u64> {
+    let point = validate_point(point, BlackBoxFunc::MultiScalarMul).ok()?;
+    let generator = ark_grumpkin::Affine::generator();
+
+    let mut multiple = ark_grumpkin::Projective::zero();
+    for s in 0..=max {
+        if multiple == point {
+            return Some(s);
+        }
+        multiple += generator;
+    }
+    None
+}
+
+/// Returns the canonical generator of the grumpkin curve.
+pub fn grumpkin_generator() -> [FieldElement; 3] {
+    affine_to_point(ark_grumpkin::Affine::generator())
+}
+
+/// Returns the canonical encoding of the point at infinity, `(0, 0, 1)`.
+pub fn canonical_infinity() -> [FieldElement; 3] {
+    affine_to_point(ark_grumpkin::Affine::zero())
+}
+
+/// Maps any encoding of the point at infinity to [canonical_infinity], and returns finite points
+/// unchanged after validating them.
+///
+/// The point at infinity is encoded either with the `is_infinite` flag set (whatever the
+/// coordinates are) or as `(0, 0)` without the flag, which isn't a point on the curve.
+pub fn normalize_point(
+    point: [FieldElement; 3],
+) -> Result<[FieldElement; 3], BlackBoxResolutionError> {
+    if point[0].is_zero() && point[1].is_zero() && point[2].is_zero() {
+        return Ok(canonical_infinity());
+    }
+    validate_point(point, BlackBoxFunc::MultiScalarMul).map(affine_to_point)
+}
+
+/// Checks whether `point` is the canonical generator of the grumpkin curve.
+pub fn is_generator(point: [FieldElement; 3]) -> Result<bool, BlackBoxResolutionError> {
+    let point = validate_point(point, BlackBoxFunc::MultiScalarMul)?;
+    Ok(point == ark_grumpkin::Affine::generator())
+}
+
+/// Checks whether `point` is in the coset of the prime order subgroup given by `representative`,
+/// that is, whether `point - representative` is in the prime order subgroup.
+///
+/// Grumpkin has cofactor 1, so every point on the curve is in the prime order subgroup and any two
+/// valid points are in the same coset. Points which aren't on the curve are rejected.
+pub fn is_in_coset(
+    point: [FieldElement; 3],
+    representative: [FieldElement; 3],
+) -> Result<bool, BlackBoxResolutionError> {
+    let point = validate_point(point, BlackBoxFunc::MultiScalarMul)?;
+    let representative = validate_point(representative, BlackBoxFunc::MultiScalarMul)?;
+    let difference = (ark_grumpkin::Projective::from(point) - representative).into_affine();
+    Ok(difference.is_in_correct_subgroup_assuming_on_curve())
+}
+
+/// Checks whether `point` is torsion-free, that is, whether multiplying it by the order of the
+/// prime order subgroup gives the point at infinity.
+///
+/// Grumpkin has cofactor 1, so this is equivalent to the subgroup check done when validating
+/// `point` and holds for every point on the curve, but it makes the check explicit.
+pub fn is_torsion_free(point: [FieldElement; 3]) -> Result<bool, BlackBoxResolutionError> {
+    let point = validate_point(point, BlackBoxFunc::MultiScalarMul)?;
+    Ok(point.mul_bigint(ark_grumpkin::Fr::MODULUS).is_zero())
+}
+
+/// Checks that `point` is a valid point other than the point at infinity, as required for
+/// example for public keys.
+///
+/// `(0, 0)` is not on the curve, so a point encoding zero coordinates without the infinity flag
+/// is rejected as well.
+pub fn assert_not_infinity(point: [FieldElement; 3]) -> Result<(), BlackBoxResolutionError> {
+    let point = validate_point(point, BlackBoxFunc::MultiScalarMul)?;
+    if point.is_zero() {
+        return Err(BlackBoxResolutionError::Failed(
+            BlackBoxFunc::MultiScalarMul,
+            "Point must not be the point at infinity".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Deterministically hashes `input` to a point on the grumpkin curve.
+///
+/// This uses the same try-and-increment algorithm used to derive generators, so the
+/// resulting point is on the curve and in the correct subgroup.
+pub fn hash_to_curve(input: &[u8]) -> [FieldElement; 3] {
+    affine_to_point(generator::hash_to_curve::hash_to_curve(input, 0))
+}
+
+/// Maps `u` to a point on the grumpkin curve with the Shallue-van de Woestijne method, as specified
+/// in RFC 9380 (simplified SWU doesn't apply directly to curves like grumpkin where `a = 0`).
+///
+/// This is only the `map_to_curve` step of hashing to the curve, so it can be composed into other
+/// hash-to-curve constructions. As grumpkin has cofactor 1, no cofactor clearing is needed afterwards.
+pub fn map_to_curve(u: FieldElement) -> Result<[FieldElement; 3], BlackBoxResolutionError> {
+    use ark_grumpkin::Fq;
+
+    let u = u.into_repr();
+    let z = svdw_z();
+    let g_z = curve_equation_rhs(z);
+    let three_z_squared = Fq::from(3u8) * z.square();
+
+    // The constants `c1` to `c4` of the method
+    let c1 = g_z;
+    let c2 = -z / Fq::from(2u8);
+    let c3 = (-g_z * three_z_squared).sqrt().expect("Z is chosen so that this is a square");
+    let c3 = if sgn0(c3) { -c3 } else { c3 };
+    let c4 = -Fq::from(4u8) * g_z / three_z_squared;
+
+    let tv1 = u.square() * c1;
+    let tv2 = Fq::ONE + tv1;
+    let tv1 = Fq::ONE - tv1;
+    let tv3 = (tv1 * tv2).inverse().unwrap_or_default();
+    let tv4 = u * tv1 * tv3 * c3;
+
+    let x1 = c2 - tv4;
+    let x2 = c2 + tv4;
+    let x3 = (tv2.square() * tv3).square() * c4 + z;
+    let x = if curve_equation_rhs(x1).legendre().is_qr() {
+        x1
+    } else if curve_equation_rhs(x2).legendre().is_qr() {
+        x2
+    } else {
+        x3
+    };
+
+    let Some(y) = curve_equation_rhs(x).sqrt() else {
+        return Err(BlackBoxResolutionError::Failed(
+            BlackBoxFunc::MultiScalarMul,
+            format!("Could not map {} to the curve", FieldElement::from_repr(u).to_hex()),
+        ));
+    };
+    let y = if sgn0(u) == sgn0(y) { y } else { -y };
+    Ok([FieldElement::from_repr(x), FieldElement::from_repr(y), FieldElement::zero()])
+}
+
+/// Computes `x^3 + ax + b`, the right hand side of grumpkin's curve equation.
+fn curve_equation_rhs(x: ark_grumpkin::Fq) -> ark_grumpkin::Fq {
+    x.square() * x
+        + ark_grumpkin::GrumpkinConfig::COEFF_A * x
+        + ark_grumpkin::GrumpkinConfig::COEFF_B
+}
+
+/// Returns the `Z` constant of the Shallue-van de Woestijne method for grumpkin, found like the
+/// `find_z_svdw` procedure of RFC 9380 does (the first `Z` in `1, -1, 2, -2, ...` that works).
+fn svdw_z() -> ark_grumpkin::Fq {
+    use ark_grumpkin::{Fq, GrumpkinConfig};
+
+    let is_square = |value: Fq| value.legendre().is_qr();
+    (1u64..)
+        .flat_map(|n| [Fq::from(n), -Fq::from(n)])
+        .find(|&z| {
+            let g_z = curve_equation_rhs(z);
+            if g_z.is_zero() {
+                return false;
+            }
+            let three_z_squared_plus_4a =
+                Fq::from(3u8) * z.square() + Fq::from(4u8) * GrumpkinConfig::COEFF_A;
+            let h_z = -three_z_squared_plus_4a / (Fq::from(4u8) * g_z);
+            !h_z.is_zero()
+                && is_square(h_z)
+                && (is_square(g_z) || is_square(curve_equation_rhs(-z / Fq::from(2u8))))
+        })
+        .expect("There is always a suitable Z")
+}
+
+/// The `sgn0` function of RFC 9380, which for prime fields is whether the value is odd.
+fn sgn0(value: ark_grumpkin::Fq) -> bool {
+    value.into_bigint().is_odd()
+}
+
+/// Converts an affine point into an `[x, y, is_infinite]` triple.
+fn affine_to_point(point: ark_grumpkin::Affine) -> [FieldElement; 3] {
+    let (x, y, is_infinite) = affine_to_triple(point);
+    [x, y, is_infinite]
+}
+
+/// Parses a point from the decimal representation of its coordinates, checking that it is on the curve.
+///
+/// This is handy for test vectors coming from other libraries, which often use decimal.
+pub fn point_from_dec_strings(
+    x: &str,
+    y: &str,
+) -> Result<[FieldElement; 3], BlackBoxResolutionError> {
+    let point = [field_from_dec_str(x)?, field_from_dec_str(y)?, FieldElement::zero()];
+    validate_point(point, BlackBoxFunc::MultiScalarMul).map(affine_to_point)
+}
+
+/// Parses a point from the hex representation of its coordinates (with or without a `0x` prefix),
+/// checking that it is on the curve and in the correct subgroup.
+pub fn point_from_hex(
+    x_hex: &str,
+    y_hex: &str,
+) -> Result<[FieldElement; 3], BlackBoxResolutionError> {
+    let point = [field_from_hex_str(x_hex)?, field_from_hex_str(y_hex)?, FieldElement::zero()];
+    validate_point(point, BlackBoxFunc::MultiScalarMul).map(affine_to_point)
+}
+
+/// Solves the curve equation for the `y` coordinates of the points with the given `x` coordinate,
+/// returning both roots, the smaller one (as an integer) first.
+///
+/// The two roots are negatives of each other. Fails if `x` isn't the `x` coordinate of a point on the curve.
+pub fn recover_both_y(
+    x: FieldElement,
+) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
+    let Some((smaller, larger)) = ark_grumpkin::Affine::get_ys_from_x_unchecked(x.into_repr())
+    else {
+        return Err(BlackBoxResolutionError::Failed(
+            BlackBoxFunc::MultiScalarMul,
+            format!("{} is not the x coordinate of a point on the curve", x.to_hex()),
+        ));
+    };
+    Ok((FieldElement::from_repr(smaller), FieldElement::from_repr(larger)))
+}
+
+/// Parses a decimal string into a field element, rejecting values which aren't smaller than the modulus.
+fn field_from_dec_str(value: &str) -> Result<FieldElement, BlackBoxResolutionError> {
+    BigInt::<4>::from_str(value)
+        .ok()
+        .and_then(ark_bn254::Fr::from_bigint)
+        .map(FieldElement::from_repr)
+        .ok_or_else(|| {
+            BlackBoxResolutionError::Failed(
+                BlackBoxFunc::MultiScalarMul,
+                format!("{value} is not a valid decimal field element"),
+            )
+        })
+}
+
+/// Parses a hex string (with or without a `0x` prefix) into a field element, rejecting values which
+/// aren't smaller than the modulus.
+fn field_from_hex_str(value: &str) -> Result<FieldElement, BlackBoxResolutionError> {
+    let digits = value.strip_prefix("0x").unwrap_or(value).to_ascii_lowercase();
+    // `from_hex` reduces its input, so check that the value round-trips to catch unreduced ones.
+    FieldElement::from_hex(&digits)
+        .filter(|field| field.to_hex().trim_start_matches('0') == digits.trim_start_matches('0'))
+        .ok_or_else(|| {
+            BlackBoxResolutionError::Failed(
+                BlackBoxFunc::MultiScalarMul,
+                format!("{value} is not a valid hex field element"),
+            )
+        })
+}
+
+/// Parses big-endian bytes into a field element, rejecting values which aren't smaller than the modulus.
+fn field_from_be_bytes(bytes: &[u8]) -> Result<FieldElement, BlackBoxResolutionError> {
+    let field = FieldElement::from_be_bytes_reduce(bytes);
+    if field.to_be_bytes() != bytes {
+        return Err(BlackBoxResolutionError::Failed(
+            BlackBoxFunc::MultiScalarMul,
+            format!("0x{} is not a valid field element", hex::encode(bytes)),
+        ));
+    }
+    Ok(field)
+}
+
+fn create_point(
+    x: FieldElement,
+    y: FieldElement,
+    is_infinite: FieldElement,
+) -> Result<ark_grumpkin::Affine, String> {
+    if is_infinite.is_one() {
+        return Ok(ark_grumpkin::Affine::zero());
+    } else if !is_infinite.is_zero() {
+        return Err("`is_infinite` flag is non-boolean".to_string());
+    }
+
+    let point = ark_grumpkin::Affine::new_unchecked(x.into_repr(), y.into_repr());
+    if !point.is_on_curve() {
+        return Err(format!("Point ({}, {}) is not on curve", x.to_hex(), y.to_hex()));
+    };
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(format!("Point ({}, {}) is not in correct subgroup", x.to_hex(), y.to_hex()));
+    };
+    Ok(point)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grumpkin_scalar::field_to_u128_limb;
+    use ark_ff::MontConfig;
+    use num_bigint::BigUint;
+    use proptest::prelude::*;
+
+    fn get_generator() -> [FieldElement; 3] {
+        let generator = ark_grumpkin::Affine::generator();
+        let generator_x = FieldElement::from_repr(generator.x().unwrap());
+        let generator_y = FieldElement::from_repr(generator.y().unwrap());
+        [generator_x, generator_y, FieldElement::zero()]
+    }
+
+    fn scalar_mul(point: [FieldElement; 3], scalar: u128) -> [FieldElement; 3] {
+        let (x, y, is_infinite) =
+            multi_scalar_mul(&point, &[FieldElement::from(scalar)], &[FieldElement::zero()])
+                .unwrap();
+        [x, y, is_infinite]
+    }
+
+    #[test]
+    fn smoke_test() -> Result<(), BlackBoxResolutionError> {
+        // We check that multiplying 1 by generator results in the generator
+        let generator = get_generator();
+
+        let res = multi_scalar_mul(&generator, &[FieldElement::one()], &[FieldElement::zero()])?;
+
+        assert_eq!(generator[0], res.0);
+        assert_eq!(generator[1], res.1);
+        Ok(())
+    }
+
+    #[test]
+    fn low_high_smoke_test() -> Result<(), BlackBoxResolutionError> {
+        let points = get_generator();
+        let scalars_lo = [FieldElement::one()];
+        let scalars_hi = [FieldElement::from(2u128)];
+
+        let res = multi_scalar_mul(&points, &scalars_lo, &scalars_hi)?;
+        let x = "0702ab9c7038eeecc179b4f209991bcb68c7cb05bf4c532d804ccac36199c9a9";
+        let y = "23f10e9e43a3ae8d75d24154e796aae12ae7af546716e8f81a2564f1b5814130";
+
+        assert_eq!(x, res.0.to_hex());
         assert_eq!(y, res.1.to_hex());
         Ok(())
     }
@@ -525,4 +1460,783 @@ mod tests {
             msm_against_add_and_mul(&points, &scalars_lo, &scalars_hi);
         }
     }
+
+    fn grumpkin_modulus() -> BigUint {
+        ark_grumpkin::FrConfig::MODULUS.into()
+    }
+
+    /// Splits a scalar into the `(lo, hi)` 128-bit limbs expected by `multi_scalar_mul`.
+    fn scalar_to_limbs(scalar: &BigUint) -> (FieldElement, FieldElement) {
+        let lo = u128::try_from(scalar & BigUint::from(u128::MAX)).unwrap();
+        let hi = u128::try_from(scalar >> 128).unwrap();
+        (FieldElement::from(lo), FieldElement::from(hi))
+    }
+
+    fn msm_single(
+        point: [FieldElement; 3],
+        scalar: &BigUint,
+    ) -> (FieldElement, FieldElement, FieldElement) {
+        let (lo, hi) = scalar_to_limbs(scalar);
+        multi_scalar_mul(&point, &[lo], &[hi]).unwrap()
+    }
+
+    prop_compose! {
+        // Sampling the high limb strictly below the modulus' high limb keeps the scalar in range.
+        fn grumpkin_scalar()
+            (lo in any::<u128>(), hi in 0..u128::try_from(grumpkin_modulus() >> 128).unwrap())
+            -> BigUint
+        {
+            (BigUint::from(hi) << 128) + BigUint::from(lo)
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn multi_scalar_mul_is_linear_in_scalars(k in 1..u64::MAX, a in grumpkin_scalar(), b in grumpkin_scalar()) {
+            // `embedded_curve_add` rejects infinite inputs, which only a zero scalar can produce here.
+            prop_assume!(a != BigUint::from(0u32) && b != BigUint::from(0u32));
+
+            let (x, y, is_infinite) = msm_single(get_generator(), &BigUint::from(k));
+            let point = [x, y, is_infinite];
+
+            // `a + b` may exceed the modulus, in which case it wraps around.
+            let sum = (&a + &b) % grumpkin_modulus();
+
+            let lhs = msm_single(point, &sum);
+            let (a_x, a_y, a_infinite) = msm_single(point, &a);
+            let (b_x, b_y, b_infinite) = msm_single(point, &b);
+            let rhs = embedded_curve_add([a_x, a_y, a_infinite], [b_x, b_y, b_infinite]).unwrap();
+
+            prop_assert_eq!(lhs, rhs);
+        }
+    }
+
+    #[test]
+    fn fixed_msm_matches_slice_msm() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let scalar = (FieldElement::from(7u128), FieldElement::from(3u128));
+
+        let fixed_res = multi_scalar_mul_fixed([generator], [scalar])?;
+        let slice_res = multi_scalar_mul(&generator, &[scalar.0], &[scalar.1])?;
+        assert_eq!(fixed_res, slice_res);
+
+        let (x, y, is_infinite) =
+            multi_scalar_mul(&generator, &[FieldElement::from(2u128)], &[FieldElement::zero()])?;
+        let point2 = [x, y, is_infinite];
+        let scalar2 = (FieldElement::from(11u128), FieldElement::zero());
+
+        let fixed_res = multi_scalar_mul_fixed([generator, point2], [scalar, scalar2])?;
+        let points: Vec<_> = generator.into_iter().chain(point2).collect();
+        let slice_res = multi_scalar_mul(&points, &[scalar.0, scalar2.0], &[scalar.1, scalar2.1])?;
+        assert_eq!(fixed_res, slice_res);
+        Ok(())
+    }
+
+    #[test]
+    fn verifies_msm_equation() -> Result<(), BlackBoxResolutionError> {
+        let points = get_generator();
+        let scalars_lo = [FieldElement::from(2u128)];
+        let scalars_hi = [FieldElement::zero()];
+
+        let (x, y, is_infinite) = embedded_curve_add(points, points)?;
+        assert!(verify_msm(&points, &scalars_lo, &scalars_hi, [x, y, is_infinite])?);
+
+        assert!(!verify_msm(&points, &scalars_lo, &scalars_hi, points)?);
+
+        let infinity = [FieldElement::zero(), FieldElement::zero(), FieldElement::one()];
+        assert!(!verify_msm(&points, &scalars_lo, &scalars_hi, infinity)?);
+        assert!(verify_msm(&points, &[FieldElement::zero()], &scalars_hi, infinity)?);
+        Ok(())
+    }
+
+    #[test]
+    fn hash_to_curve_is_deterministic() {
+        let point = hash_to_curve(b"hello world");
+
+        assert!(create_point(point[0], point[1], point[2]).is_ok());
+        assert_eq!(point, hash_to_curve(b"hello world"));
+        assert_eq!(
+            point[0].to_hex(),
+            "037c5c229ae495f6e8d1b4bf7723fafb2b198b51e27602feb8a4d1053d685093"
+        );
+        assert_eq!(
+            point[1].to_hex(),
+            "10cf9596c5b2515692d930efa2cf3817607e4796856a79f6af40c949b066969f"
+        );
+        assert_eq!(point[2], FieldElement::zero());
+    }
+
+    #[test]
+    fn small_multiples_match_scalar_mul() -> Result<(), BlackBoxResolutionError> {
+        let point = scalar_mul(get_generator(), 3);
+
+        let multiples = small_multiples(point, 5)?;
+
+        assert_eq!(multiples.len(), 5);
+        for (k, (x, y, is_infinite)) in multiples.into_iter().enumerate() {
+            assert_eq!([x, y, is_infinite], scalar_mul(point, k as u128 + 1));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_inconsistent_infinity_encoding_when_pedantic() {
+        let malformed_infinity =
+            [FieldElement::from(5u128), FieldElement::zero(), FieldElement::one()];
+        let points = [get_generator(), malformed_infinity].concat();
+
+        let res = multi_scalar_mul(&points, &[FieldElement::one(); 2], &[FieldElement::zero(); 2]);
+
+        assert_eq!(
+            res,
+            Err(BlackBoxResolutionError::Failed(
+                BlackBoxFunc::MultiScalarMul,
+                "Point at index 1 is flagged as infinite but has non-zero coordinates (0000000000000000000000000000000000000000000000000000000000000005, 0000000000000000000000000000000000000000000000000000000000000000)".into(),
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_inconsistent_infinity_encoding() {
+        let generator = get_generator();
+        let malformed_infinity =
+            [FieldElement::from(5u128), FieldElement::zero(), FieldElement::one()];
+        let canonical_infinity = [FieldElement::zero(), FieldElement::zero(), FieldElement::one()];
+
+        let valid_points = [generator, canonical_infinity].concat();
+        assert_eq!(validate_infinity_encoding(&valid_points), Ok(()));
+
+        let points = [generator, malformed_infinity].concat();
+        assert_eq!(
+            validate_infinity_encoding(&points),
+            Err(BlackBoxResolutionError::Failed(
+                BlackBoxFunc::MultiScalarMul,
+                "Point at index 1 is flagged as infinite but has non-zero coordinates (0000000000000000000000000000000000000000000000000000000000000005, 0000000000000000000000000000000000000000000000000000000000000000)".into(),
+            ))
+        );
+    }
+
+    #[test]
+    fn const_scalar_msm_matches_generic_msm() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let points = [generator, scalar_mul(generator, 2), scalar_mul(generator, 3)].concat();
+        let scalars = [
+            GrumpkinScalar::from(0u128),
+            GrumpkinScalar::from(1u128),
+            -GrumpkinScalar::from(12345u128),
+        ];
+
+        let (scalars_lo, scalars_hi): (Vec<_>, Vec<_>) =
+            scalars.iter().map(|scalar| scalar.to_limbs()).unzip();
+        let expected = multi_scalar_mul(&points, &scalars_lo, &scalars_hi)?;
+
+        let const_scalars = scalars.map(ConstScalar::from);
+        assert_eq!(multi_scalar_mul_const(&points, &const_scalars)?, expected);
+        Ok(())
+    }
+
+    #[cfg(feature = "debug-tools")]
+    #[test]
+    fn window_sums_combine_to_msm_result() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let points = [generator, scalar_mul(generator, 5), scalar_mul(generator, 9)].concat();
+        let scalars = [
+            GrumpkinScalar::from(3u128),
+            -GrumpkinScalar::from(1u128),
+            GrumpkinScalar::from(1u128 << 100),
+        ];
+        let (scalars_lo, scalars_hi): (Vec<_>, Vec<_>) =
+            scalars.iter().map(|scalar| scalar.to_limbs()).unzip();
+
+        let window_bits = 5;
+        let window_sums = msm_window_sums(&points, &scalars_lo, &scalars_hi, window_bits)?;
+
+        let mut combined = ark_grumpkin::Projective::zero();
+        for window_sum in window_sums.into_iter().rev() {
+            for _ in 0..window_bits {
+                combined.double_in_place();
+            }
+            combined += validate_point(window_sum, BlackBoxFunc::MultiScalarMul)?;
+        }
+
+        let expected = multi_scalar_mul(&points, &scalars_lo, &scalars_hi)?;
+        assert_eq!(affine_to_triple(combined.into()), expected);
+
+        for window_bits in [0, 17] {
+            assert!(msm_window_sums(&points, &scalars_lo, &scalars_hi, window_bits).is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn subtracting_blinding_recovers_msm_result() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let points = [generator, scalar_mul(generator, 7)].concat();
+        let scalars_lo = [FieldElement::from(11u128), FieldElement::from(13u128)];
+        let scalars_hi = [FieldElement::zero(), FieldElement::one()];
+        let blinding = scalar_mul(generator, 42);
+
+        let (x, y, is_infinite) =
+            multi_scalar_mul_blinded(&points, &scalars_lo, &scalars_hi, blinding)?;
+        let negated_blinding = [blinding[0], -blinding[1], blinding[2]];
+        let unblinded = embedded_curve_add([x, y, is_infinite], negated_blinding)?;
+
+        assert_eq!(unblinded, multi_scalar_mul(&points, &scalars_lo, &scalars_hi)?);
+        Ok(())
+    }
+
+    #[test]
+    fn parses_point_from_decimal_strings() -> Result<(), BlackBoxResolutionError> {
+        let point = point_from_dec_strings(
+            "1",
+            "17631683881184975370165255887551781615748388533673675138860",
+        )?;
+        assert_eq!(point, get_generator());
+
+        assert_eq!(
+            point_from_dec_strings("0x1", "2"),
+            Err(BlackBoxResolutionError::Failed(
+                BlackBoxFunc::MultiScalarMul,
+                "0x1 is not a valid decimal field element".into(),
+            ))
+        );
+        assert!(point_from_dec_strings("1", "2").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn chunked_msm_matches_msm() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let points: Vec<_> = (1..=10).flat_map(|k| scalar_mul(generator, k)).collect();
+        let scalars_lo: Vec<_> =
+            (0..10u128).map(|i| FieldElement::from(i * 1_000_003 + 1)).collect();
+        let scalars_hi: Vec<_> = (0..10u128).map(FieldElement::from).collect();
+
+        let expected = multi_scalar_mul(&points, &scalars_lo, &scalars_hi)?;
+        assert_eq!(multi_scalar_mul_chunked(&points, &scalars_lo, &scalars_hi, 3)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn recognizes_generator() -> Result<(), BlackBoxResolutionError> {
+        assert_eq!(grumpkin_generator(), get_generator());
+        assert!(is_generator(grumpkin_generator())?);
+        assert!(!is_generator(scalar_mul(grumpkin_generator(), 2))?);
+        Ok(())
+    }
+
+    #[test]
+    fn accumulates_points_in_projective_form() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let mut acc = ark_grumpkin::Projective::zero();
+        for _ in 0..3 {
+            accumulate(&mut acc, generator)?;
+        }
+        assert_eq!(affine_to_point(acc.into()), scalar_mul(generator, 3));
+        Ok(())
+    }
+
+    #[test]
+    fn linear_combination_matches_msm() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let points = [generator, scalar_mul(generator, 3), scalar_mul(generator, 8)];
+        let coeffs = [
+            (FieldElement::from(4u128), FieldElement::zero()),
+            (FieldElement::from(9u128), FieldElement::one()),
+            (FieldElement::from(u128::MAX), FieldElement::from(2u128)),
+        ];
+
+        let (scalars_lo, scalars_hi): (Vec<_>, Vec<_>) = coeffs.into_iter().unzip();
+        let expected = multi_scalar_mul(&points.concat(), &scalars_lo, &scalars_hi)?;
+        assert_eq!(linear_combination(&points, &coeffs)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn msm_into_reused_buffers_matches_msm() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let points = [generator, scalar_mul(generator, 7)].concat();
+        let scalars_lo = [FieldElement::from(3u128), FieldElement::from(5u128)];
+        let scalars_hi = [FieldElement::zero(), FieldElement::one()];
+
+        let mut bases_buf = Vec::new();
+        let mut ints_buf = Vec::new();
+        let res = multi_scalar_mul_into(
+            &points,
+            &scalars_lo,
+            &scalars_hi,
+            &mut bases_buf,
+            &mut ints_buf,
+        )?;
+        assert_eq!(res, multi_scalar_mul(&points, &scalars_lo, &scalars_hi)?);
+
+        // The second call must not see the bases and scalars of the first one.
+        let res = multi_scalar_mul_into(
+            &generator,
+            &[FieldElement::from(2u128)],
+            &[FieldElement::zero()],
+            &mut bases_buf,
+            &mut ints_buf,
+        )?;
+        assert_eq!(<[FieldElement; 3]>::from(res), scalar_mul(generator, 2));
+        assert_eq!(bases_buf.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_point_at_infinity() {
+        assert_eq!(assert_not_infinity(get_generator()), Ok(()));
+
+        let infinity = [FieldElement::zero(), FieldElement::zero(), FieldElement::one()];
+        assert_eq!(
+            assert_not_infinity(infinity),
+            Err(BlackBoxResolutionError::Failed(
+                BlackBoxFunc::MultiScalarMul,
+                "Point must not be the point at infinity".into(),
+            ))
+        );
+
+        let zero = [FieldElement::zero(); 3];
+        assert!(assert_not_infinity(zero).is_err());
+    }
+
+    #[test]
+    fn msm_then_add_matches_adding_to_msm_result() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let points = [generator, scalar_mul(generator, 5)].concat();
+        let scalars_lo = [FieldElement::from(3u128), FieldElement::from(4u128)];
+        let scalars_hi = [FieldElement::one(), FieldElement::zero()];
+        let addend = scalar_mul(generator, 11);
+
+        let (x, y, is_infinite) = multi_scalar_mul(&points, &scalars_lo, &scalars_hi)?;
+        let expected = embedded_curve_add([x, y, is_infinite], addend)?;
+        assert_eq!(msm_then_add(&points, &scalars_lo, &scalars_hi, addend)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn msm_sub_is_infinity_only_for_equal_msms() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let points = [generator, scalar_mul(generator, 2)].concat();
+        let scalars_lo = [FieldElement::from(3u128), FieldElement::from(4u128)];
+        let scalars_hi = [FieldElement::zero(), FieldElement::one()];
+        let infinity = (FieldElement::zero(), FieldElement::zero(), FieldElement::one());
+
+        let res = msm_sub(&points, &scalars_lo, &scalars_hi, &points, &scalars_lo, &scalars_hi)?;
+        assert_eq!(res, infinity);
+
+        // [3]G + [4 + 2^128]([2]G) - [11]G == [2^129]G
+        let (lo, hi) = (FieldElement::from(11u128), FieldElement::zero());
+        let res = msm_sub(&points, &scalars_lo, &scalars_hi, &generator, &[lo], &[hi])?;
+        assert_ne!(res, infinity);
+        let expected =
+            multi_scalar_mul(&generator, &[FieldElement::zero()], &[FieldElement::from(2u128)])?;
+        assert_eq!(res, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn normalizes_infinity_encodings() -> Result<(), BlackBoxResolutionError> {
+        let infinity = canonical_infinity();
+        assert_eq!(infinity, [FieldElement::zero(), FieldElement::zero(), FieldElement::one()]);
+
+        let flagged = [FieldElement::from(5u128), FieldElement::from(7u128), FieldElement::one()];
+        assert_eq!(normalize_point(flagged)?, infinity);
+        assert_eq!(normalize_point([FieldElement::zero(); 3])?, infinity);
+        assert_eq!(normalize_point(get_generator())?, get_generator());
+
+        let not_on_curve = [FieldElement::one(), FieldElement::one(), FieldElement::zero()];
+        assert!(normalize_point(not_on_curve).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn repeated_doubling_matches_scalar_mul() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let (x, y, is_infinite) = double_repeated(generator, 3)?;
+        assert_eq!([x, y, is_infinite], scalar_mul(generator, 8));
+
+        let (x, y, is_infinite) = double_repeated(generator, 0)?;
+        assert_eq!([x, y, is_infinite], generator);
+        Ok(())
+    }
+
+    #[test]
+    fn verifies_batch_of_additions() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let two_g = scalar_mul(generator, 2);
+        let three_g = scalar_mul(generator, 3);
+        let not_on_curve = [FieldElement::one(), FieldElement::one(), FieldElement::zero()];
+
+        let results = verify_adds(&[
+            (generator, two_g, three_g),
+            (generator, two_g, two_g),
+            (two_g, generator, three_g),
+            (generator, generator, not_on_curve),
+        ])?;
+        assert_eq!(results, vec![true, false, true, false]);
+
+        assert!(verify_adds(&[(not_on_curve, generator, three_g)]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn soa_msm_matches_interleaved_msm() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let infinity = [FieldElement::zero(), FieldElement::zero(), FieldElement::one()];
+        let points = [generator, scalar_mul(generator, 6), infinity];
+        let scalars_lo =
+            [FieldElement::from(2u128), FieldElement::from(9u128), FieldElement::one()];
+        let scalars_hi = [FieldElement::one(), FieldElement::zero(), FieldElement::zero()];
+
+        let xs = points.map(|point| point[0]);
+        let ys = points.map(|point| point[1]);
+        let infs = points.map(|point| point[2]);
+        assert_eq!(
+            multi_scalar_mul_soa(&xs, &ys, &infs, &scalars_lo, &scalars_hi)?,
+            multi_scalar_mul(&points.concat(), &scalars_lo, &scalars_hi)?
+        );
+
+        assert!(multi_scalar_mul_soa(&xs[..2], &ys, &infs, &scalars_lo, &scalars_hi).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn opening_commitment_leaves_remaining_values() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let generators = [generator, scalar_mul(generator, 3), scalar_mul(generator, 10)].concat();
+        let values =
+            [FieldElement::from(5u128), FieldElement::from(u128::MAX), FieldElement::from(7u128)];
+        let zero = [FieldElement::zero(); 3];
+
+        let (x, y, is_infinite) = multi_scalar_mul(&generators, &values, &zero)?;
+        let residual = open_commitment([x, y, is_infinite], 1, values[1], &generators)?;
+
+        let remaining_values = [values[0], FieldElement::zero(), values[2]];
+        let (x, y, is_infinite) = multi_scalar_mul(&generators, &remaining_values, &zero)?;
+        assert_eq!(residual, [x, y, is_infinite]);
+
+        assert!(open_commitment([x, y, is_infinite], 3, values[1], &generators).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn selects_point_depending_on_condition() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let infinity = [FieldElement::zero(), FieldElement::zero(), FieldElement::one()];
+
+        assert_eq!(conditional_select(true, generator, infinity)?, generator);
+        assert_eq!(conditional_select(false, generator, infinity)?, infinity);
+
+        let not_on_curve = [FieldElement::one(), FieldElement::one(), FieldElement::zero()];
+        assert!(conditional_select(true, generator, not_on_curve).is_err());
+        assert!(conditional_select(false, not_on_curve, generator).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn compares_point_arrays() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let two_g = scalar_mul(generator, 2);
+        let infinity = [FieldElement::zero(), FieldElement::zero(), FieldElement::one()];
+        let flagged_infinity =
+            [FieldElement::from(5u128), FieldElement::from(7u128), FieldElement::one()];
+
+        let a = [generator, two_g, infinity].concat();
+        assert!(point_arrays_equal(&a, &[generator, two_g, flagged_infinity].concat())?);
+        assert!(!point_arrays_equal(&a, &[generator, generator, infinity].concat())?);
+        assert!(point_arrays_equal(&a, &[generator, two_g].concat()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn parses_point_from_hex_strings() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let x_hex = generator[0].to_hex();
+        let y_hex = format!("0x{}", generator[1].to_hex());
+        assert_eq!(point_from_hex(&x_hex, &y_hex)?, generator);
+
+        // The modulus itself isn't a valid coordinate, even though it reduces to zero.
+        let modulus = "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+        assert_eq!(
+            point_from_hex(modulus, &y_hex),
+            Err(BlackBoxResolutionError::Failed(
+                BlackBoxFunc::MultiScalarMul,
+                format!("{modulus} is not a valid hex field element"),
+            ))
+        );
+        assert!(point_from_hex("0x1", "0x2").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn recovers_small_discrete_log() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        assert_eq!(small_discrete_log(scalar_mul(generator, 5), 10), Some(5));
+        assert_eq!(small_discrete_log(scalar_mul(generator, 5), 4), None);
+
+        let points = [generator, scalar_mul(generator, 4)].concat();
+        let scalars_lo = [FieldElement::from(3u128), FieldElement::from(2u128)];
+        let (x, y, is_infinite) =
+            multi_scalar_mul(&points, &scalars_lo, &[FieldElement::zero(); 2])?;
+        assert_eq!(small_discrete_log([x, y, is_infinite], 100), Some(11));
+        assert_eq!(small_discrete_log([x, y, is_infinite], 11), Some(11));
+        Ok(())
+    }
+
+    #[test]
+    fn double_negation_is_identity() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let infinity = [FieldElement::zero(), FieldElement::zero(), FieldElement::one()];
+        let points = [generator, scalar_mul(generator, 9), infinity].concat();
+
+        let negated = negate_batch(&points)?;
+        assert_eq!(negate_batch(&negated)?, points);
+
+        // Each point plus its negation is the point at infinity
+        let scalars = [FieldElement::one(); 6];
+        let sum = multi_scalar_mul(
+            &[points.clone(), negated].concat(),
+            &scalars,
+            &[FieldElement::zero(); 6],
+        )?;
+        assert_eq!(sum, (infinity[0], infinity[1], infinity[2]));
+
+        assert!(negate_batch(&points[..4]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn double_scalar_mul_matches_two_term_msm() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let q = scalar_mul(generator, 7);
+        let infinity = [FieldElement::zero(), FieldElement::zero(), FieldElement::one()];
+        let a = (FieldElement::from(u128::MAX), FieldElement::from(1u128 << 100));
+        let b = (FieldElement::from(12345u128), FieldElement::from(3u128));
+        let zero = (FieldElement::zero(), FieldElement::zero());
+
+        for (p, a, q, b) in
+            [(generator, a, q, b), (generator, zero, q, b), (infinity, a, q, b), (q, a, q, a)]
+        {
+            let expected = multi_scalar_mul(&[p, q].concat(), &[a.0, b.0], &[a.1, b.1])?;
+            assert_eq!(double_scalar_mul(p, a, q, b)?, expected);
+        }
+
+        let invalid = (FieldElement::zero(), FieldElement::from(u128::MAX));
+        assert!(double_scalar_mul(generator, invalid, q, b).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn round_trips_points_through_bytes() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let infinity = [FieldElement::zero(), FieldElement::zero(), FieldElement::one()];
+        let points = [generator, infinity].concat();
+
+        let bytes = points_to_bytes(&points)?;
+        assert_eq!(bytes.len(), 128);
+        assert_eq!(bytes[..32], generator[0].to_be_bytes());
+        assert!(bytes[64..].iter().all(|byte| *byte == 0));
+        assert_eq!(points_from_bytes(&bytes)?, points);
+
+        // Not a whole number of points, or an unreduced x coordinate
+        assert!(points_from_bytes(&bytes[..100]).is_err());
+        let mut unreduced = bytes.clone();
+        unreduced[..32].copy_from_slice(&ark_bn254::FrConfig::MODULUS.to_bytes_be());
+        assert!(points_from_bytes(&unreduced).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn recovers_both_y_coordinates() -> Result<(), BlackBoxResolutionError> {
+        for point in [get_generator(), scalar_mul(get_generator(), 3)] {
+            let (smaller, larger) = recover_both_y(point[0])?;
+            assert_eq!(smaller, -larger);
+            assert!(smaller.into_repr().into_bigint() < larger.into_repr().into_bigint());
+            assert!(point[1] == smaller || point[1] == larger);
+            for y in [smaller, larger] {
+                let point = [point[0], y, FieldElement::zero()];
+                assert!(validate_point(point, BlackBoxFunc::MultiScalarMul).is_ok());
+            }
+        }
+
+        let invalid_x = (0u128..).map(FieldElement::from).find(|x| recover_both_y(*x).is_err());
+        assert!(invalid_x.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn checks_coset_membership() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let infinity = [FieldElement::zero(), FieldElement::zero(), FieldElement::one()];
+
+        // With cofactor 1 there's a single coset, the whole curve
+        assert!(is_in_coset(scalar_mul(generator, 5), generator)?);
+        assert!(is_in_coset(generator, infinity)?);
+        assert!(is_in_coset(infinity, infinity)?);
+
+        let off_curve = [FieldElement::one(), FieldElement::one(), FieldElement::zero()];
+        assert!(is_in_coset(off_curve, generator).is_err());
+        assert!(is_in_coset(generator, off_curve).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn compressed_msm_result_decompresses_to_msm_result() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let points = [generator, scalar_mul(generator, 3)].concat();
+        let scalars_hi = [FieldElement::zero(), FieldElement::one()];
+
+        for scalars_lo in
+            [[FieldElement::from(2u128), FieldElement::from(5u128)], [FieldElement::from(7u128); 2]]
+        {
+            let bytes = multi_scalar_mul_to_bytes(&points, &scalars_lo, &scalars_hi)?;
+
+            let x = FieldElement::from_be_bytes_reduce(&bytes[1..]);
+            let (smaller, larger) = recover_both_y(x)?;
+            let y = [smaller, larger]
+                .into_iter()
+                .find(|y| y.into_repr().into_bigint().is_odd() == (bytes[0] == 0x03))
+                .unwrap();
+            let expected = multi_scalar_mul(&points, &scalars_lo, &scalars_hi)?;
+            assert_eq!((x, y, FieldElement::zero()), expected);
+        }
+
+        let zeros = [FieldElement::zero(); 2];
+        assert_eq!(multi_scalar_mul_to_bytes(&points, &zeros, &zeros)?, [0; 33]);
+        Ok(())
+    }
+
+    #[test]
+    fn partial_sums_match_msm_of_terms_so_far() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let points = [generator, scalar_mul(generator, 3), scalar_mul(generator, 10)];
+        let scalars_lo =
+            [FieldElement::from(4u128), FieldElement::from(u128::MAX), FieldElement::zero()];
+        let scalars_hi = [FieldElement::zero(), FieldElement::from(2u128), FieldElement::one()];
+
+        let mut verifier = MsmPartialVerifier::new();
+        assert_eq!(
+            verifier.partial_sum(),
+            (FieldElement::zero(), FieldElement::zero(), FieldElement::one())
+        );
+        for i in 0..points.len() {
+            let partial_sum = verifier.add_term(points[i], scalars_lo[i], scalars_hi[i])?;
+            let expected =
+                multi_scalar_mul(&points[..=i].concat(), &scalars_lo[..=i], &scalars_hi[..=i])?;
+            assert_eq!(partial_sum, expected);
+            assert_eq!(verifier.partial_sum(), expected);
+        }
+
+        let invalid_point = [FieldElement::one(), FieldElement::one(), FieldElement::zero()];
+        assert!(
+            verifier.add_term(invalid_point, FieldElement::one(), FieldElement::zero()).is_err()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rotates_points() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let (a, b, c) = (generator, scalar_mul(generator, 2), scalar_mul(generator, 3));
+        let points = [a, b, c].concat();
+
+        assert_eq!(rotate_multiples(&points, 1)?, [b, c, a].concat());
+        assert_eq!(rotate_multiples(&points, 3)?, points);
+        assert_eq!(rotate_multiples(&points, 5)?, [c, a, b].concat());
+        assert_eq!(rotate_multiples(&[], 1)?, vec![]);
+
+        assert!(rotate_multiples(&points[..8], 1).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn maps_field_elements_onto_curve() -> Result<(), BlackBoxResolutionError> {
+        let inputs = [0u128, 1, 2, 3, 17, 12345, u128::MAX].map(FieldElement::from);
+        for u in inputs.into_iter().chain([-FieldElement::one()]) {
+            let point = map_to_curve(u)?;
+            assert!(validate_point(point, BlackBoxFunc::MultiScalarMul).is_ok());
+            assert_eq!(map_to_curve(u)?, point);
+
+            // The sign of `y` follows the one of `u`
+            assert_eq!(sgn0(point[1].into_repr()), sgn0(u.into_repr()));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn sparse_msm_matches_dense_msm() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let points: Vec<_> = (1..=5).flat_map(|i| scalar_mul(generator, i)).collect();
+        let nonzero = [
+            (1, FieldElement::from(7u128), FieldElement::zero()),
+            (4, FieldElement::from(2u128), FieldElement::one()),
+        ];
+
+        let mut scalars_lo = vec![FieldElement::zero(); 5];
+        let mut scalars_hi = vec![FieldElement::zero(); 5];
+        for (index, scalar_lo, scalar_hi) in nonzero {
+            scalars_lo[index] = scalar_lo;
+            scalars_hi[index] = scalar_hi;
+        }
+        let (x, y, is_infinite) = multi_scalar_mul(&points, &scalars_lo, &scalars_hi)?;
+        assert_eq!(sparse_msm(&points, &nonzero)?, [x, y, is_infinite]);
+        assert_eq!(sparse_msm(&points, &[])?, canonical_infinity());
+
+        let out_of_bounds = [(5, FieldElement::one(), FieldElement::zero())];
+        assert!(sparse_msm(&points, &out_of_bounds).is_err());
+        let invalid_scalar = [(0, FieldElement::zero(), -FieldElement::one())];
+        assert!(sparse_msm(&points, &invalid_scalar).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn valid_points_are_torsion_free() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        for point in [generator, scalar_mul(generator, 12345), canonical_infinity()] {
+            assert!(is_torsion_free(point)?);
+        }
+
+        let off_curve = [FieldElement::one(), FieldElement::one(), FieldElement::zero()];
+        assert!(is_torsion_free(off_curve).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn batch_scalar_mul_matches_individual_scalar_muls() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let point = scalar_mul(generator, 7);
+        let pairs = [
+            (generator, (FieldElement::from(3u128), FieldElement::zero())),
+            (point, (FieldElement::from(11u128), FieldElement::zero())),
+            (point, (FieldElement::zero(), FieldElement::zero())),
+            (canonical_infinity(), (FieldElement::from(5u128), FieldElement::zero())),
+        ];
+
+        let expected = vec![
+            scalar_mul(generator, 3),
+            scalar_mul(point, 11),
+            scalar_mul(point, 0),
+            scalar_mul(canonical_infinity(), 5),
+        ];
+        assert_eq!(batch_scalar_mul(&pairs)?, expected);
+        assert!(batch_scalar_mul(&[])?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn msm_terms_sum_to_zero_only_when_they_cancel() -> Result<(), BlackBoxResolutionError> {
+        let generator = get_generator();
+        let points = [generator, generator].concat();
+        let (minus_one_lo, minus_one_hi) = scalar_to_limbs(&(grumpkin_modulus() - 1u32));
+
+        let scalars_lo = [FieldElement::one(), minus_one_lo];
+        let scalars_hi = [FieldElement::zero(), minus_one_hi];
+        assert!(msm_terms_sum_to_zero(&points, &scalars_lo, &scalars_hi)?);
+
+        let scalars_lo = [FieldElement::one(), FieldElement::from(2u128)];
+        let scalars_hi = [FieldElement::zero(), FieldElement::zero()];
+        assert!(!msm_terms_sum_to_zero(&points, &scalars_lo, &scalars_hi)?);
+        Ok(())
+    }
 }
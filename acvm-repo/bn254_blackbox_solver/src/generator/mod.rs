@@ -5,4 +5,4 @@
 //! [barustenberg]: https://github.com/laudiacay/barustenberg/blob/df6bc6f095fe7f288bf6a12e7317fd8eb33d68ae/
 
 pub(crate) mod generators;
-mod hash_to_curve;
+pub(crate) mod hash_to_curve;
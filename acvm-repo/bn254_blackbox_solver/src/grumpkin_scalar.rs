@@ -0,0 +1,373 @@
+use acir::{AcirField, BlackBoxFunc};
+use ark_ff::{BigInt, BigInteger, Field, MontConfig, One, PrimeField, Zero};
+
+use crate::{BlackBoxResolutionError, FieldElement};
+
+/// A scalar of the Grumpkin curve.
+///
+/// Grumpkin's scalar field is BN254's base field, so a scalar doesn't fit in a [FieldElement]
+/// (which holds point coordinates) and is passed around as a pair of 128-bit `(lo, hi)` limbs instead.
+/// Wrapping it in this type keeps scalars from being mixed up with coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrumpkinScalar(ark_grumpkin::Fr);
+
+impl GrumpkinScalar {
+    /// Returns the `(lo, hi)` 128-bit limbs of this scalar.
+    pub fn to_limbs(self) -> (FieldElement, FieldElement) {
+        let limbs = self.0.into_bigint().0;
+        let lo = u128::from(limbs[0]) | (u128::from(limbs[1]) << 64);
+        let hi = u128::from(limbs[2]) | (u128::from(limbs[3]) << 64);
+        (FieldElement::from(lo), FieldElement::from(hi))
+    }
+
+    pub(crate) fn into_bigint(self) -> BigInt<4> {
+        self.0.into_bigint()
+    }
+
+    pub(crate) fn is_zero(self) -> bool {
+        self.0.is_zero()
+    }
+
+    pub(crate) fn is_one(self) -> bool {
+        self.0.is_one()
+    }
+}
+
+/// Marks a scalar which is known before solving, for example because it is a constant of the circuit.
+///
+/// An MSM whose scalars are all constant can be specialized (see [crate::multi_scalar_mul_const]):
+/// the scalars are validated once when the marker is built and trivial terms can be folded away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConstScalar(pub GrumpkinScalar);
+
+impl From<GrumpkinScalar> for ConstScalar {
+    fn from(scalar: GrumpkinScalar) -> Self {
+        ConstScalar(scalar)
+    }
+}
+
+impl std::ops::Neg for GrumpkinScalar {
+    type Output = GrumpkinScalar;
+
+    fn neg(self) -> Self::Output {
+        GrumpkinScalar(-self.0)
+    }
+}
+
+impl From<u128> for GrumpkinScalar {
+    fn from(value: u128) -> Self {
+        GrumpkinScalar(ark_grumpkin::Fr::from(value))
+    }
+}
+
+impl TryFrom<(FieldElement, FieldElement)> for GrumpkinScalar {
+    type Error = BlackBoxResolutionError;
+
+    /// Combines `(lo, hi)` 128-bit limbs, checking that they form a valid Grumpkin scalar.
+    fn try_from((lo, hi): (FieldElement, FieldElement)) -> Result<Self, Self::Error> {
+        let scalar_low: u128 = field_to_u128_limb(&lo, BlackBoxFunc::MultiScalarMul)?;
+
+        let scalar_high: u128 = field_to_u128_limb(&hi, BlackBoxFunc::MultiScalarMul)?;
+
+        // Convert to BigInt<4>, using u64 limbs.
+        let limbs_array = [
+            scalar_low as u64,
+            (scalar_low >> 64) as u64,
+            scalar_high as u64,
+            (scalar_high >> 64) as u64,
+        ];
+        let scalar_bigint = BigInt::new(limbs_array);
+
+        // Check if this is smaller than the grumpkin modulus
+        if scalar_bigint >= ark_grumpkin::FrConfig::MODULUS {
+            // Format as hex string (big-endian, most significant limb first)
+            let hex_str = format!(
+                "{:016x}{:016x}{:016x}{:016x}",
+                limbs_array[3], limbs_array[2], limbs_array[1], limbs_array[0]
+            );
+            return Err(BlackBoxResolutionError::Failed(
+                BlackBoxFunc::MultiScalarMul,
+                format!("{hex_str} is not a valid grumpkin scalar"),
+            ));
+        }
+
+        let scalar = ark_grumpkin::Fr::from_bigint(scalar_bigint)
+            .expect("scalar is smaller than the grumpkin modulus");
+        Ok(GrumpkinScalar(scalar))
+    }
+}
+
+impl From<GrumpkinScalar> for (FieldElement, FieldElement) {
+    fn from(scalar: GrumpkinScalar) -> Self {
+        scalar.to_limbs()
+    }
+}
+
+/// Error returned when a value can't be reinterpreted in another field because it is not smaller
+/// than that field's modulus.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldConversionError {
+    value: BigInt<4>,
+    target: &'static str,
+}
+
+impl std::fmt::Display for FieldConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = hex::encode(self.value.to_bytes_be());
+        write!(f, "{value} is not representable as a {}", self.target)
+    }
+}
+
+impl std::error::Error for FieldConversionError {}
+
+/// Reinterprets a BN254 scalar (which is what a [FieldElement] holds) as an element of Grumpkin's
+/// base field, checking that it is smaller than the base field modulus.
+///
+/// Grumpkin's base field is BN254's scalar field, so the check only fails if the two stop matching,
+/// but it keeps the change of role explicit where it happens.
+pub fn bn254_scalar_as_grumpkin_base(
+    x: FieldElement,
+) -> Result<FieldElement, FieldConversionError> {
+    let value = x.into_repr().into_bigint();
+    ark_grumpkin::Fq::from_bigint(value)
+        .map(FieldElement::from_repr)
+        .ok_or(FieldConversionError { value, target: "grumpkin base field element" })
+}
+
+/// Reinterprets an element of Grumpkin's base field as a BN254 scalar, checking that it is smaller
+/// than the BN254 scalar field modulus. This is the reverse of [bn254_scalar_as_grumpkin_base].
+pub fn grumpkin_base_as_bn254_scalar(
+    x: FieldElement,
+) -> Result<FieldElement, FieldConversionError> {
+    let value = x.into_repr().into_bigint();
+    ark_bn254::Fr::from_bigint(value)
+        .map(FieldElement::from_repr)
+        .ok_or(FieldConversionError { value, target: "bn254 scalar" })
+}
+
+/// Converts a BN254 scalar into a Grumpkin scalar, as done for the scalars of the embedded curve
+/// operations. BN254's scalar field is smaller than Grumpkin's scalar field, so the value is kept as is.
+pub(crate) fn bn254_scalar_to_grumpkin_scalar(x: FieldElement) -> GrumpkinScalar {
+    GrumpkinScalar(ark_grumpkin::Fr::from_be_bytes_mod_order(&x.to_be_bytes()))
+}
+
+/// Converts a Grumpkin scalar into a BN254 scalar, that is, into a [FieldElement].
+///
+/// Returns an error if the scalar is not smaller than the BN254 scalar field modulus.
+pub fn grumpkin_scalar_to_bn254_scalar(
+    scalar: GrumpkinScalar,
+) -> Result<FieldElement, FieldConversionError> {
+    let value = scalar.into_bigint();
+    ark_bn254::Fr::from_bigint(value)
+        .map(FieldElement::from_repr)
+        .ok_or(FieldConversionError { value, target: "bn254 scalar" })
+}
+
+/// Interprets `bytes` as a big-endian integer and reduces it modulo the grumpkin order, returning the
+/// `(lo, hi)` limbs of the result.
+///
+/// Inputs of any length are accepted, so this can map uniformly random bytes (like a hash output)
+/// to a scalar for [crate::multi_scalar_mul].
+pub fn bytes_to_grumpkin_scalar(bytes: &[u8]) -> (FieldElement, FieldElement) {
+    GrumpkinScalar(ark_grumpkin::Fr::from_be_bytes_mod_order(bytes)).to_limbs()
+}
+
+/// Negates the scalar given by its `(lo, hi)` limbs modulo the grumpkin order,
+/// returning the limbs of `order - s` (or zero if `s` is zero).
+pub fn negate_grumpkin_scalar(
+    lo: FieldElement,
+    hi: FieldElement,
+) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
+    let scalar = GrumpkinScalar::try_from((lo, hi))?;
+    Ok((-scalar).to_limbs())
+}
+
+/// Returns whether the scalars given by the `(lo1, hi1)` and `(lo2, hi2)` limbs are equal modulo
+/// the grumpkin order.
+///
+/// Unlike [GrumpkinScalar::try_from], this accepts unreduced scalars (and limbs larger than 128 bits),
+/// which makes it useful to check that a scalar was reduced correctly.
+pub fn scalars_congruent(
+    lo1: FieldElement,
+    hi1: FieldElement,
+    lo2: FieldElement,
+    hi2: FieldElement,
+) -> bool {
+    reduce_limbs(lo1, hi1) == reduce_limbs(lo2, hi2)
+}
+
+/// Returns whether the scalar given by its `(lo, hi)` limbs is invertible modulo the grumpkin order.
+///
+/// The order is prime, so this holds for every scalar which doesn't reduce to zero.
+pub fn scalar_is_invertible(lo: FieldElement, hi: FieldElement) -> bool {
+    !reduce_limbs(lo, hi).is_zero()
+}
+
+/// Returns the limbs of the inverse of the scalar given by its `(lo, hi)` limbs modulo the grumpkin order,
+/// or `None` if the scalar reduces to zero.
+pub fn invert_grumpkin_scalar(
+    lo: FieldElement,
+    hi: FieldElement,
+) -> Option<(FieldElement, FieldElement)> {
+    reduce_limbs(lo, hi).inverse().map(|inverse| GrumpkinScalar(inverse).to_limbs())
+}
+
+/// Computes `lo + hi * 2^128` modulo the grumpkin order.
+fn reduce_limbs(lo: FieldElement, hi: FieldElement) -> ark_grumpkin::Fr {
+    let two_pow_128 = ark_grumpkin::Fr::from(u128::MAX) + ark_grumpkin::Fr::one();
+    let lo = ark_grumpkin::Fr::from_be_bytes_mod_order(&lo.to_be_bytes());
+    let hi = ark_grumpkin::Fr::from_be_bytes_mod_order(&hi.to_be_bytes());
+    lo + hi * two_pow_128
+}
+
+/// Converts a field element to u128, returning an error if it doesn't fit.
+pub(crate) fn field_to_u128_limb(
+    limb: &FieldElement,
+    func: BlackBoxFunc,
+) -> Result<u128, BlackBoxResolutionError> {
+    limb.try_into_u128().ok_or_else(|| {
+        BlackBoxResolutionError::Failed(
+            func,
+            format!("Limb {} is not less than 2^128", limb.to_hex()),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_ec::AffineRepr;
+
+    use super::*;
+    use crate::multi_scalar_mul;
+
+    #[test]
+    fn round_trips_limbs() -> Result<(), BlackBoxResolutionError> {
+        let limbs = (FieldElement::from(u128::MAX), FieldElement::from(5u128));
+        let scalar = GrumpkinScalar::try_from(limbs)?;
+        assert_eq!(scalar.to_limbs(), limbs);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_out_of_range_scalar() {
+        let x = ark_grumpkin::FrConfig::MODULUS.to_bytes_be();
+        let lo = FieldElement::from_be_bytes_reduce(&x[16..32]);
+        let hi = FieldElement::from_be_bytes_reduce(&x[0..16]);
+
+        assert_eq!(
+            GrumpkinScalar::try_from((lo, hi)),
+            Err(BlackBoxResolutionError::Failed(
+                BlackBoxFunc::MultiScalarMul,
+                "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47 is not a valid grumpkin scalar".into(),
+            ))
+        );
+    }
+
+    #[test]
+    fn negated_scalar_cancels_out_in_msm() -> Result<(), BlackBoxResolutionError> {
+        let generator = ark_grumpkin::Affine::generator();
+        let generator = [
+            FieldElement::from_repr(generator.x),
+            FieldElement::from_repr(generator.y),
+            FieldElement::zero(),
+        ];
+        let points: Vec<_> = generator.into_iter().chain(generator).collect();
+
+        let (lo, hi) = (FieldElement::from(123u128), FieldElement::from(456u128));
+        let (neg_lo, neg_hi) = negate_grumpkin_scalar(lo, hi)?;
+
+        let res = multi_scalar_mul(&points, &[lo, neg_lo], &[hi, neg_hi])?;
+        assert_eq!(res, (FieldElement::zero(), FieldElement::zero(), FieldElement::one()));
+
+        assert_eq!(
+            negate_grumpkin_scalar(FieldElement::zero(), FieldElement::zero())?,
+            (FieldElement::zero(), FieldElement::zero())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn compares_scalars_modulo_the_order() {
+        let mut order_plus_five = ark_grumpkin::FrConfig::MODULUS;
+        order_plus_five.add_with_carry(&BigInt::from(5u64));
+        let bytes = order_plus_five.to_bytes_be();
+        let lo = FieldElement::from_be_bytes_reduce(&bytes[16..32]);
+        let hi = FieldElement::from_be_bytes_reduce(&bytes[0..16]);
+
+        let five = FieldElement::from(5u128);
+        assert!(scalars_congruent(lo, hi, five, FieldElement::zero()));
+        assert!(!scalars_congruent(lo, hi, FieldElement::from(6u128), FieldElement::zero()));
+        assert!(!scalars_congruent(five, FieldElement::zero(), five, FieldElement::one()));
+    }
+
+    #[test]
+    fn converts_between_bn254_scalars_and_grumpkin_base_elements()
+    -> Result<(), FieldConversionError> {
+        for value in [FieldElement::zero(), FieldElement::from(5u128), -FieldElement::one()] {
+            let base = bn254_scalar_as_grumpkin_base(value)?;
+            assert_eq!(base, value);
+            assert_eq!(grumpkin_base_as_bn254_scalar(base)?, value);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn converts_between_bn254_and_grumpkin_scalars() -> Result<(), FieldConversionError> {
+        for value in [FieldElement::from(5u128), -FieldElement::one()] {
+            let scalar = bn254_scalar_to_grumpkin_scalar(value);
+            assert_eq!(grumpkin_scalar_to_bn254_scalar(scalar)?, value);
+        }
+
+        // The largest Grumpkin scalar is larger than BN254's scalar field modulus.
+        let error = grumpkin_scalar_to_bn254_scalar(-GrumpkinScalar::from(1u128)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd46 is not representable as a bn254 scalar"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn inverted_scalar_undoes_scalar_multiplication() -> Result<(), BlackBoxResolutionError> {
+        let generator = ark_grumpkin::Affine::generator();
+        let generator = [
+            FieldElement::from_repr(generator.x),
+            FieldElement::from_repr(generator.y),
+            FieldElement::zero(),
+        ];
+
+        let (lo, hi) = (FieldElement::from(123u128), FieldElement::from(456u128));
+        assert!(scalar_is_invertible(lo, hi));
+        let (inv_lo, inv_hi) = invert_grumpkin_scalar(lo, hi).expect("scalar is invertible");
+
+        let (x, y, is_infinite) = multi_scalar_mul(&generator, &[inv_lo], &[inv_hi])?;
+        let res = multi_scalar_mul(&[x, y, is_infinite], &[lo], &[hi])?;
+        assert_eq!(res, (generator[0], generator[1], generator[2]));
+
+        // The order itself reduces to zero, so it has no inverse.
+        let order = ark_grumpkin::FrConfig::MODULUS.to_bytes_be();
+        let lo = FieldElement::from_be_bytes_reduce(&order[16..32]);
+        let hi = FieldElement::from_be_bytes_reduce(&order[0..16]);
+        assert!(!scalar_is_invertible(lo, hi));
+        assert_eq!(invert_grumpkin_scalar(lo, hi), None);
+        Ok(())
+    }
+
+    #[test]
+    fn reduces_bytes_to_grumpkin_scalar() {
+        let bytes = [0xFF; 64];
+        let (lo, hi) = bytes_to_grumpkin_scalar(&bytes);
+        assert!(GrumpkinScalar::try_from((lo, hi)).is_ok());
+
+        // 2^512 - 1 = (2^256 - 1) * 2^256 + (2^256 - 1)
+        let low_half = ark_grumpkin::Fr::from_be_bytes_mod_order(&bytes[32..]);
+        let two_pow_256 = ark_grumpkin::Fr::from(2u64).pow([256]);
+        let expected = GrumpkinScalar(low_half * two_pow_256 + low_half);
+        assert_eq!((lo, hi), expected.to_limbs());
+
+        assert_eq!(
+            bytes_to_grumpkin_scalar(&[1, 2]),
+            (FieldElement::from(0x0102u128), FieldElement::zero())
+        );
+    }
+}
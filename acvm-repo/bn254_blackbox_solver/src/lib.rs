@@ -5,12 +5,34 @@ use acvm_blackbox_solver::{BlackBoxFunctionSolver, BlackBoxResolutionError};
 
 mod embedded_curve_ops;
 mod generator;
+mod grumpkin_scalar;
+mod pedersen;
 mod poseidon2;
 mod poseidon2_constants;
+mod schnorr;
 
-pub use embedded_curve_ops::{embedded_curve_add, multi_scalar_mul};
+#[cfg(feature = "debug-tools")]
+pub use embedded_curve_ops::msm_window_sums;
+pub use embedded_curve_ops::{
+    MsmPartialVerifier, accumulate, assert_not_infinity, batch_scalar_mul, canonical_infinity,
+    conditional_select, double_repeated, double_scalar_mul, embedded_curve_add, grumpkin_generator,
+    hash_to_curve, is_generator, is_in_coset, is_torsion_free, linear_combination, map_to_curve,
+    msm_sub, msm_terms_sum_to_zero, msm_then_add, multi_scalar_mul, multi_scalar_mul_blinded,
+    multi_scalar_mul_chunked, multi_scalar_mul_const, multi_scalar_mul_fixed,
+    multi_scalar_mul_into, multi_scalar_mul_soa, multi_scalar_mul_to_bytes, negate_batch,
+    normalize_point, open_commitment, point_arrays_equal, point_from_dec_strings, point_from_hex,
+    points_from_bytes, points_to_bytes, recover_both_y, rotate_multiples, small_discrete_log,
+    small_multiples, sparse_msm, validate_infinity_encoding, verify_adds, verify_msm,
+};
 pub use generator::generators::derive_generators;
+pub use grumpkin_scalar::{
+    ConstScalar, FieldConversionError, GrumpkinScalar, bn254_scalar_as_grumpkin_base,
+    bytes_to_grumpkin_scalar, grumpkin_base_as_bn254_scalar, grumpkin_scalar_to_bn254_scalar,
+    invert_grumpkin_scalar, negate_grumpkin_scalar, scalar_is_invertible, scalars_congruent,
+};
+pub use pedersen::{hash_array_to_point, pedersen_hash, pedersen_hash_with_separator};
 pub use poseidon2::poseidon2_permutation;
+pub use schnorr::verify_schnorr;
 
 // Temporary hack, this ensure that we always use a bn254 field here
 // without polluting the feature flags of the `acir_field` crate.
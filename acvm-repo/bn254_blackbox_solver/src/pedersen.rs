@@ -0,0 +1,118 @@
+use acir::{AcirField, BlackBoxFunc};
+use ark_ec::{AffineRepr, VariableBaseMSM};
+
+use crate::embedded_curve_ops::hash_to_curve;
+use crate::generator::generators::{DEFAULT_DOMAIN_SEPARATOR, derive_generators};
+use crate::grumpkin_scalar::{GrumpkinScalar, bn254_scalar_to_grumpkin_scalar};
+use crate::{BlackBoxResolutionError, FieldElement};
+
+/// Domain separator of the generator which the number of hashed inputs is multiplied with.
+const LENGTH_DOMAIN_SEPARATOR: &[u8] = b"pedersen_hash_length";
+
+/// Computes the Pedersen hash of `inputs`, matching Noir's `std::hash::pedersen_hash`.
+///
+/// This is the x-coordinate of the Pedersen commitment to `inputs` (with generators derived from
+/// the default domain separator), plus an extra term committing to the number of inputs.
+pub fn pedersen_hash(inputs: &[FieldElement]) -> Result<FieldElement, BlackBoxResolutionError> {
+    pedersen_hash_with_separator(inputs, 0)
+}
+
+/// Computes the Pedersen hash of `inputs` with a domain separator, matching Noir's
+/// `std::hash::pedersen_hash_with_separator`.
+///
+/// `separator` is the index of the first generator derived from the default domain separator, so
+/// hashes computed with different separators use disjoint sets of generators.
+pub fn pedersen_hash_with_separator(
+    inputs: &[FieldElement],
+    separator: u32,
+) -> Result<FieldElement, BlackBoxResolutionError> {
+    let num_inputs = u32::try_from(inputs.len()).map_err(|_| {
+        BlackBoxResolutionError::Failed(
+            BlackBoxFunc::MultiScalarMul,
+            format!("Cannot hash {} inputs, at most {} are supported", inputs.len(), u32::MAX),
+        )
+    })?;
+
+    let mut bases = derive_generators(DEFAULT_DOMAIN_SEPARATOR, num_inputs, separator);
+    bases.extend(derive_generators(LENGTH_DOMAIN_SEPARATOR, 1, 0));
+
+    let scalars: Vec<_> = inputs
+        .iter()
+        .map(|input| bn254_scalar_to_grumpkin_scalar(*input))
+        .chain(std::iter::once(GrumpkinScalar::from(u128::from(num_inputs))))
+        .map(GrumpkinScalar::into_bigint)
+        .collect();
+
+    let commitment: ark_grumpkin::Affine =
+        ark_grumpkin::Projective::msm_bigint(&bases, &scalars).into();
+    Ok(commitment.x().map_or_else(FieldElement::zero, FieldElement::from_repr))
+}
+
+/// Deterministically maps `inputs` to a point on the grumpkin curve, for example to use as a set
+/// element in membership gadgets.
+///
+/// The point is the [hash_to_curve] of the big-endian bytes of the [pedersen_hash] of `inputs`.
+pub fn hash_array_to_point(
+    inputs: &[FieldElement],
+) -> Result<[FieldElement; 3], BlackBoxResolutionError> {
+    let hash = pedersen_hash(inputs)?;
+    Ok(hash_to_curve(&hash.to_be_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedded_curve_ops::assert_not_infinity;
+
+    #[test]
+    fn matches_pedersen_hash_test_vector() -> Result<(), BlackBoxResolutionError> {
+        // See `test_programs/execution_success/pedersen_hash`.
+        let expected = FieldElement::from_hex(
+            "0x0d98561fb02ca04d00801dfdc118b2a24cea0351963587712a28d368041370e1",
+        )
+        .unwrap();
+
+        let hash = pedersen_hash(&[FieldElement::zero(), FieldElement::one()])?;
+        assert_eq!(hash, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn matches_pedersen_hash_with_separator_test_vector() -> Result<(), BlackBoxResolutionError> {
+        // See `assert_pedersen` in `noir_stdlib/src/hash/mod.nr`.
+        let expected = FieldElement::from_hex(
+            "0x1b3f4b1a83092a13d8d1a59f7acb62aba15e7002f4440f2275edb99ebbc2305f",
+        )
+        .unwrap();
+
+        let hash = pedersen_hash_with_separator(&[FieldElement::one()], 1)?;
+        assert_eq!(hash, expected);
+        assert_ne!(hash, pedersen_hash(&[FieldElement::one()])?);
+        Ok(())
+    }
+
+    #[test]
+    fn hashes_array_to_stable_point_on_curve() -> Result<(), BlackBoxResolutionError> {
+        let inputs =
+            [FieldElement::from(1u128), FieldElement::from(2u128), FieldElement::from(3u128)];
+        let expected = [
+            FieldElement::from_hex(
+                "0x120f7424f9cb402d413e9af32f54dc3a807eb34ca8fc3ccd8643e1315aa17ac8",
+            )
+            .unwrap(),
+            FieldElement::from_hex(
+                "0x1afe0d10bd9b0084d339b1fa9852ff0f771db856fea37cb1c25026fc422a09cc",
+            )
+            .unwrap(),
+            FieldElement::zero(),
+        ];
+
+        let point = hash_array_to_point(&inputs)?;
+        assert_eq!(point, expected);
+        assert_not_infinity(point)?;
+
+        let reversed = [inputs[2], inputs[1], inputs[0]];
+        assert_ne!(hash_array_to_point(&reversed)?, point);
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+//! Verification of Schnorr signatures over the grumpkin curve, for witness-side signature checks.
+//!
+//! A signature `(s, e)` over `message` is valid for the public key `P` if, with `R = [s]G + [e]P`,
+//! `R` is not the point at infinity and `e == blake3(R.x || P.x || P.y || message)`, where field
+//! elements are encoded as 32 big-endian bytes and the hash is reduced modulo the field modulus.
+//! A signature for the private key `x` is made with a random nonce `k` by taking `R = [k]G`
+//! and `s = k - e * x` (modulo the grumpkin order).
+use acir::AcirField;
+use acvm_blackbox_solver::blake3;
+
+use crate::grumpkin_scalar::bn254_scalar_to_grumpkin_scalar;
+use crate::{
+    BlackBoxResolutionError, FieldElement, assert_not_infinity, grumpkin_generator,
+    multi_scalar_mul,
+};
+
+/// Verifies the Schnorr `signature` over `message` for `pubkey`.
+///
+/// Returns an error if `pubkey` is not a valid point or is the point at infinity: for that key
+/// `R = [s]G` whatever `e` is, so anyone could forge a signature.
+pub fn verify_schnorr(
+    pubkey: [FieldElement; 3],
+    message: &[u8],
+    signature: (FieldElement, FieldElement),
+) -> Result<bool, BlackBoxResolutionError> {
+    assert_not_infinity(pubkey)?;
+
+    let (s, e) = signature;
+    let (s_lo, s_hi) = bn254_scalar_to_grumpkin_scalar(s).to_limbs();
+    let (e_lo, e_hi) = bn254_scalar_to_grumpkin_scalar(e).to_limbs();
+
+    let points = [grumpkin_generator(), pubkey].concat();
+    let (r_x, _, r_is_infinite) = multi_scalar_mul(&points, &[s_lo, e_lo], &[s_hi, e_hi])?;
+    if r_is_infinite.is_one() {
+        return Ok(false);
+    }
+
+    Ok(challenge(r_x, pubkey, message)? == e)
+}
+
+/// Computes `blake3(r_x || pubkey.x || pubkey.y || message)`, reduced to a field element.
+fn challenge(
+    r_x: FieldElement,
+    pubkey: [FieldElement; 3],
+    message: &[u8],
+) -> Result<FieldElement, BlackBoxResolutionError> {
+    let mut preimage = Vec::with_capacity(3 * 32 + message.len());
+    for value in [r_x, pubkey[0], pubkey[1]] {
+        preimage.extend(value.to_be_bytes());
+    }
+    preimage.extend_from_slice(message);
+    Ok(FieldElement::from_be_bytes_reduce(&blake3(&preimage)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_ff::PrimeField;
+
+    use super::*;
+
+    fn grumpkin_scalar(value: FieldElement) -> ark_grumpkin::Fr {
+        ark_grumpkin::Fr::from_bigint(value.into_repr().into_bigint()).unwrap()
+    }
+
+    fn sign(private_key: u128, nonce: u128, message: &[u8]) -> (FieldElement, FieldElement) {
+        let g = grumpkin_generator();
+        let (pub_x, pub_y, _) =
+            multi_scalar_mul(&g, &[private_key.into()], &[FieldElement::zero()]).unwrap();
+        let (r_x, _, _) = multi_scalar_mul(&g, &[nonce.into()], &[FieldElement::zero()]).unwrap();
+
+        let e = challenge(r_x, [pub_x, pub_y, FieldElement::zero()], message).unwrap();
+        let s = ark_grumpkin::Fr::from(nonce)
+            - grumpkin_scalar(e) * ark_grumpkin::Fr::from(private_key);
+        let s =
+            ark_bn254::Fr::from_bigint(s.into_bigint()).expect("s should fit in a field element");
+        (FieldElement::from_repr(s), e)
+    }
+
+    #[test]
+    fn verifies_schnorr_signature() -> Result<(), BlackBoxResolutionError> {
+        let private_key = 12345u128;
+        let message = b"hello world";
+        let signature = sign(private_key, 6789, message);
+
+        let (pub_x, pub_y, _) = multi_scalar_mul(
+            &grumpkin_generator(),
+            &[private_key.into()],
+            &[FieldElement::zero()],
+        )?;
+        let pubkey = [pub_x, pub_y, FieldElement::zero()];
+
+        assert!(verify_schnorr(pubkey, message, signature)?);
+        assert!(!verify_schnorr(pubkey, b"hello there", signature)?);
+
+        let (s, e) = signature;
+        assert!(!verify_schnorr(pubkey, message, (s + FieldElement::one(), e))?);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_pubkey_at_infinity() {
+        let pubkey = [FieldElement::zero(), FieldElement::zero(), FieldElement::one()];
+        let message = b"hello world";
+
+        // With `P = O`, `R = [s]G` so the challenge can be computed for any `s`.
+        let s = FieldElement::from(42u128);
+        let (r_x, _, _) =
+            multi_scalar_mul(&grumpkin_generator(), &[s], &[FieldElement::zero()]).unwrap();
+        let e = challenge(r_x, pubkey, message).unwrap();
+
+        assert!(verify_schnorr(pubkey, message, (s, e)).is_err());
+    }
+
+    #[test]
+    fn rejects_pubkey_not_on_curve() {
+        let pubkey = [FieldElement::one(), FieldElement::one(), FieldElement::zero()];
+        let signature = sign(12345, 6789, b"hello world");
+        assert!(verify_schnorr(pubkey, b"hello world", signature).is_err());
+    }
+}
@@ -45,12 +45,14 @@ use rayon::prelude::*;
 use rustc_hash::FxHashSet;
 
 use notifications::{
-    on_did_change_configuration, on_did_change_text_document, on_did_close_text_document,
-    on_did_open_text_document, on_did_save_text_document, on_exit, on_initialized,
+    on_cancel_request, on_did_change_configuration, on_did_change_text_document,
+    on_did_close_text_document, on_did_open_text_document, on_did_save_text_document, on_exit,
+    on_initialized,
 };
 use requests::{
-    LspInitializationOptions, WorkspaceSymbolCache, on_code_action_request, on_code_lens_request,
-    on_completion_request, on_document_symbol_request, on_formatting, on_goto_declaration_request,
+    CancellationToken, LspInitializationOptions, ParameterCandidatesCache, WorkspaceSymbolCache,
+    on_code_action_request, on_code_lens_request, on_completion_request,
+    on_document_symbol_request, on_formatting, on_goto_declaration_request,
     on_goto_definition_request, on_goto_type_definition_request, on_hover_request, on_initialize,
     on_inlay_hint_request, on_prepare_rename_request, on_references_request, on_rename_request,
     on_shutdown, on_signature_help_request, on_test_run_request, on_tests_request,
@@ -100,10 +102,15 @@ pub struct LspState {
     client: ClientSocket,
     solver: WrapperSolver,
     input_files: HashMap<String, String>,
+    // Tracks the version of each open document, by URI.
+    document_versions: HashMap<String, i32>,
     cached_parsed_files: HashMap<PathBuf, (usize, (ParsedModule, Vec<ParserError>))>,
     workspace_cache: HashMap<PathBuf, WorkspaceCacheData>,
     package_cache: HashMap<PathBuf, PackageCacheData>,
     workspace_symbol_cache: WorkspaceSymbolCache,
+    parameter_candidates_cache: ParameterCandidatesCache,
+    // Cancelled when the client cancels the completion request being processed.
+    completion_cancellation: CancellationToken,
 
     options: LspInitializationOptions,
 
@@ -133,10 +140,13 @@ impl LspState {
             root_path: None,
             solver: WrapperSolver(Box::new(solver)),
             input_files: HashMap::new(),
+            document_versions: HashMap::new(),
             cached_parsed_files: HashMap::new(),
             workspace_cache: HashMap::new(),
             package_cache: HashMap::new(),
             workspace_symbol_cache: WorkspaceSymbolCache::default(),
+            parameter_candidates_cache: ParameterCandidatesCache::default(),
+            completion_cancellation: CancellationToken::default(),
             options: Default::default(),
             files_with_errors: HashMap::new(),
         }
@@ -179,6 +189,7 @@ impl NargoLspService {
             .request::<NargoExpand, _>(on_expand_request)
             .request::<NargoStdSourceCode, _>(on_std_source_code_request)
             .notification::<notification::Initialized>(on_initialized)
+            .notification::<notification::Cancel>(on_cancel_request)
             .notification::<notification::DidChangeConfiguration>(on_did_change_configuration)
             .notification::<notification::DidOpenTextDocument>(on_did_open_text_document)
             .notification::<notification::DidChangeTextDocument>(on_did_change_text_document)
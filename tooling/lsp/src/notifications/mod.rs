@@ -25,9 +25,10 @@ use noirc_frontend::hir::def_map::{CrateDefMap, LocalModuleId};
 use noirc_frontend::parse_program;
 
 use crate::types::{
-    Diagnostic, DiagnosticSeverity, DidChangeConfigurationParams, DidChangeTextDocumentParams,
-    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams,
-    InitializedParams, NargoPackageTests, PublishDiagnosticsParams, notification,
+    CancelParams, Diagnostic, DiagnosticSeverity, DidChangeConfigurationParams,
+    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    DidSaveTextDocumentParams, InitializedParams, NargoPackageTests, PublishDiagnosticsParams,
+    notification,
 };
 
 use crate::{
@@ -49,11 +50,24 @@ pub(super) fn on_did_change_configuration(
     ControlFlow::Continue(())
 }
 
+pub(super) fn on_cancel_request(
+    state: &mut LspState,
+    _params: CancelParams,
+) -> ControlFlow<Result<(), async_lsp::Error>> {
+    // Only completion requests check for cancellation, so there's no need to track which
+    // request is being cancelled
+    state.completion_cancellation.cancel();
+    ControlFlow::Continue(())
+}
+
 pub(crate) fn on_did_open_text_document(
     state: &mut LspState,
     params: DidOpenTextDocumentParams,
 ) -> ControlFlow<Result<(), async_lsp::Error>> {
     state.input_files.insert(params.text_document.uri.to_string(), params.text_document.text);
+    state
+        .document_versions
+        .insert(params.text_document.uri.to_string(), params.text_document.version);
 
     let document_uri = params.text_document.uri;
 
@@ -69,6 +83,9 @@ pub(super) fn on_did_change_text_document(
 ) -> ControlFlow<Result<(), async_lsp::Error>> {
     let text = params.content_changes.into_iter().next().unwrap().text;
     state.input_files.insert(params.text_document.uri.to_string(), text.clone());
+    state
+        .document_versions
+        .insert(params.text_document.uri.to_string(), params.text_document.version);
     state.workspace_symbol_cache.reprocess_uri(&params.text_document.uri);
 
     let document_uri = params.text_document.uri;
@@ -84,6 +101,8 @@ pub(super) fn on_did_close_text_document(
     params: DidCloseTextDocumentParams,
 ) -> ControlFlow<Result<(), async_lsp::Error>> {
     state.input_files.remove(&params.text_document.uri.to_string());
+    state.document_versions.remove(&params.text_document.uri.to_string());
+    state.parameter_candidates_cache.remove_document(&params.text_document.uri.to_string());
     state.workspace_symbol_cache.reprocess_uri(&params.text_document.uri);
 
     let document_uri = params.text_document.uri;
@@ -52,7 +52,7 @@ use crate::{
     utils,
 };
 
-use super::{TraitReexport, process_request};
+use super::{CancellationToken, CompletionsOptions, TraitReexport, process_request};
 
 mod auto_import;
 mod builtins;
@@ -62,10 +62,31 @@ mod params;
 mod sort_text;
 mod tests;
 
+use params::DocumentParameterCandidates;
+pub(crate) use params::ParameterCandidatesCache;
+
 pub(crate) fn on_completion_request(
     state: &mut LspState,
     params: CompletionParams,
 ) -> impl Future<Output = Result<Option<CompletionResponse>, ResponseError>> + use<> {
+    // A cancellation that arrived after the previous request finished doesn't apply to this one
+    state.completion_cancellation = CancellationToken::default();
+    let cancellation = state.completion_cancellation.clone();
+    future::ready(completion_response(state, params, cancellation))
+}
+
+/// Computes the completions for `params`, stopping early (with the completions found so far) once
+/// `cancellation` is cancelled.
+fn completion_response(
+    state: &mut LspState,
+    params: CompletionParams,
+    cancellation: CancellationToken,
+) -> Result<Option<CompletionResponse>, ResponseError> {
+    let options = state.options.completions;
+    let mut parameter_candidates_cache = std::mem::take(&mut state.parameter_candidates_cache);
+    let uri = params.text_document_position.text_document.uri.to_string();
+    let version = state.document_versions.get(&uri).copied();
+
     let result = process_request(state, params.text_document_position.clone(), |args| {
         let file_id = args.location.file;
         utils::position_to_byte_index(args.files, file_id, &params.text_document_position.position)
@@ -74,6 +95,8 @@ pub(crate) fn on_completion_request(
                 let source = file.source();
                 let byte = source.as_bytes().get(byte_index - 1).copied();
                 let (parsed_module, _errors) = noirc_frontend::parse_program(source, file_id);
+                let parameter_candidates =
+                    parameter_candidates_cache.document(uri, version, source);
 
                 let mut finder = NodeFinder::new(
                     args.files,
@@ -85,11 +108,15 @@ pub(crate) fn on_completion_request(
                     args.def_maps,
                     args.dependencies(),
                     args.interner,
+                    options,
+                    cancellation,
+                    parameter_candidates,
                 );
                 finder.find(&parsed_module)
             })
     });
-    future::ready(result)
+    state.parameter_candidates_cache = parameter_candidates_cache;
+    result
 }
 
 struct NodeFinder<'a> {
@@ -105,8 +132,16 @@ struct NodeFinder<'a> {
     def_maps: &'a BTreeMap<CrateId, CrateDefMap>,
     dependencies: &'a Vec<Dependency>,
     interner: &'a NodeInterner,
+    options: CompletionsOptions,
+    /// Cancelled if the client doesn't need this request's completions anymore.
+    cancellation: CancellationToken,
+    /// Parameters found in previous completion requests for the same version of the document.
+    parameter_candidates: &'a mut DocumentParameterCandidates,
     /// Completion items we find along the way.
     completion_items: Vec<CompletionItem>,
+    /// The name and full type of each `name: Type` parameter suggestion, by its index in
+    /// `completion_items`.
+    suggested_parameter_types: HashMap<usize, (String, String)>,
     /// Local variables in the current scope, mapped to their locations.
     /// As we traverse the AST, we collect local variables.
     local_variables: HashMap<String, Span>,
@@ -138,6 +173,9 @@ impl<'a> NodeFinder<'a> {
         def_maps: &'a BTreeMap<CrateId, CrateDefMap>,
         dependencies: &'a Vec<Dependency>,
         interner: &'a NodeInterner,
+        options: CompletionsOptions,
+        cancellation: CancellationToken,
+        parameter_candidates: &'a mut DocumentParameterCandidates,
     ) -> Self {
         // Find the module the current file belongs to
         let def_map = &def_maps[&krate];
@@ -160,7 +198,11 @@ impl<'a> NodeFinder<'a> {
             def_maps,
             dependencies,
             interner,
+            options,
+            cancellation,
+            parameter_candidates,
             completion_items: Vec::new(),
+            suggested_parameter_types: HashMap::new(),
             local_variables: HashMap::new(),
             type_parameters: HashSet::new(),
             suggested_module_def_ids: HashSet::new(),
@@ -1336,6 +1378,10 @@ impl Visitor for NodeFinder<'_> {
     }
 
     fn visit_noir_trait_impl(&mut self, noir_trait_impl: &NoirTraitImpl, _: Span) -> bool {
+        if self.try_complete_function_param_in_trait_impl(noir_trait_impl) {
+            return false;
+        }
+
         for generic in &noir_trait_impl.impl_generics {
             generic.accept(self);
         }
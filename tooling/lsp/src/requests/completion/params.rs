@@ -1,14 +1,123 @@
 //! If the cursor is at the end of a function parameter name, suggest parameter names (and their types)
-//! that exists in the same module, impl or trait.
-use std::collections::HashSet;
+//! that exists in the same module, impl or trait, or that the function's type, body or doc comments
+//! hint at. If the cursor is right after the parameter's colon, suggest the types that parameters
+//! with that name have instead.
+use std::{
+    collections::{HashMap, HashSet},
+    hash::BuildHasher,
+};
 
+use noirc_errors::{Location, Span};
 use noirc_frontend::{
     ParsedModule,
-    ast::{NoirFunction, NoirTrait, Pattern, TraitItem, TypeImpl, UnresolvedTypeData},
+    ast::{
+        CallExpression, DocComment, ExpressionKind, ForLoopStatement, GenericTypeArgs, Ident,
+        IdentOrQuotedType, IfExpression, LetStatement, NoirFunction, NoirTrait, NoirTraitImpl,
+        Param, Path, Pattern, TraitImplItemKind, TraitItem, TypeImpl, UnresolvedGeneric,
+        UnresolvedType, UnresolvedTypeData, Visitor,
+    },
+    elaborator::PrimitiveType,
+    hir::def_map::ModuleDefId,
+    hir_def::stmt::HirPattern,
+    node_interner::{NodeInterner, ReferenceId},
     parser::ItemKind,
 };
 
-use crate::requests::completion::{NodeFinder, name_matches, variable_completion_item};
+use async_lsp::lsp_types::{CompletionItem, CompletionItemKind, CompletionItemTag};
+use convert_case::{Case, Casing};
+use iter_extended::vecmap;
+use rustc_hash::FxBuildHasher;
+
+use crate::requests::{
+    CancellationToken, ParameterNameStyle, ParameterRanking,
+    completion::{
+        NodeFinder,
+        completion_items::{
+            completion_item_with_detail, completion_item_with_sort_text, simple_completion_item,
+            snippet_completion_item,
+        },
+        name_matches,
+        sort_text::{ranked_parameter_sort_text, self_receiver_sort_text},
+        variable_completion_item,
+    },
+};
+
+/// The names and types of the parameters found in each scope (module or impl) of a document, by
+/// the start of the scope's first function.
+pub(super) type DocumentParameterCandidates = HashMap<usize, Vec<(String, String)>>;
+
+/// The parameters found in each document, kept between completion requests until the document
+/// changes.
+#[derive(Default)]
+pub(crate) struct ParameterCandidatesCache {
+    /// The revision of each document the parameters were found in (its version, if it's open,
+    /// and the hash of its source), and the parameters, by URI.
+    documents: HashMap<String, ((Option<i32>, u64), DocumentParameterCandidates)>,
+}
+
+impl ParameterCandidatesCache {
+    /// Returns the parameters cached for the document at `uri`, after discarding them if they
+    /// weren't found in this `version` and `source` of it.
+    pub(super) fn document(
+        &mut self,
+        uri: String,
+        version: Option<i32>,
+        source: &str,
+    ) -> &mut DocumentParameterCandidates {
+        let revision = (version, FxBuildHasher.hash_one(source));
+        let (cached_revision, candidates) =
+            self.documents.entry(uri).or_insert_with(|| (revision, HashMap::new()));
+        if *cached_revision != revision {
+            *cached_revision = revision;
+            candidates.clear();
+        }
+        candidates
+    }
+
+    pub(crate) fn remove_document(&mut self, uri: &str) {
+        self.documents.remove(uri);
+    }
+}
+
+/// The parameter being completed: `parameter` of `function`, with `name` so far.
+struct FunctionParam<'a> {
+    function: &'a NoirFunction,
+    parameter: &'a Param,
+    name: &'a str,
+    /// The doc comments of `function`, if they were found.
+    doc_comments: Option<&'a [DocComment]>,
+}
+
+/// Bounds a single pass of the search for parameters to suggest, so that completion stays
+/// responsive in very large modules. A pass stops once it looked at as many functions as the
+/// `maxParameterSearchFunctions` option allows, or as soon as the request is cancelled, and the
+/// suggestions found until then are still returned.
+struct ParameterSearchBudget {
+    remaining_functions: usize,
+    cancellation: CancellationToken,
+}
+
+impl ParameterSearchBudget {
+    /// Counts another function being looked at, unless the pass has to stop before it.
+    fn take_function(&mut self) -> bool {
+        if self.remaining_functions == 0 || self.cancellation.is_cancelled() {
+            return false;
+        }
+        self.remaining_functions -= 1;
+        true
+    }
+}
+
+/// Where the function whose parameter is being completed is defined.
+#[derive(Clone, Copy)]
+enum FunctionParamScope<'a> {
+    /// A module, where functions generated by macros aren't part of the parsed source.
+    Module,
+    /// A type impl, where methods can take `self`.
+    TypeImpl,
+    /// A trait impl, where methods can take `self` and the trait's default methods are in scope.
+    TraitImpl(&'a NoirTraitImpl),
+}
 
 impl NodeFinder<'_> {
     pub(super) fn try_complete_function_param_in_parsed_module(
@@ -19,16 +128,20 @@ impl NodeFinder<'_> {
             if let ItemKind::Function(function) = &item.kind { Some(function) } else { None }
         });
 
-        let function_and_name =
-            find_function_and_parameter_name_at_byte_index(functions.clone(), self.byte_index);
-        let Some((function, name)) = function_and_name else {
-            return false;
+        let function_and_parameter =
+            find_function_and_parameter_at_byte_index(functions.clone(), self.byte_index);
+        let Some((function, parameter, name)) = function_and_parameter else {
+            return self.try_complete_function_param_type(function_parameters(functions));
         };
+        let doc_comments = parsed_module.items.iter().find_map(|item| match &item.kind {
+            ItemKind::Function(item_function) if std::ptr::eq(item_function, function) => {
+                Some(item.doc_comments.as_slice())
+            }
+            _ => None,
+        });
 
-        let names_to_exclude = names_to_exclude(function, name);
-
-        self.suggest_function_parameters(functions, name, names_to_exclude);
-
+        let param = FunctionParam { function, parameter, name, doc_comments };
+        self.complete_function_param(functions, param, FunctionParamScope::Module);
         true
     }
 
@@ -39,16 +152,48 @@ impl NodeFinder<'_> {
         let functions =
             type_impl.methods.iter().map(|(documented_method, _)| &documented_method.item);
 
-        let function_and_name =
-            find_function_and_parameter_name_at_byte_index(functions.clone(), self.byte_index);
-        let Some((function, name)) = function_and_name else {
-            return false;
+        let function_and_parameter =
+            find_function_and_parameter_at_byte_index(functions.clone(), self.byte_index);
+        let Some((function, parameter, name)) = function_and_parameter else {
+            return self.try_complete_function_param_type(function_parameters(functions));
         };
+        let doc_comments = type_impl.methods.iter().find_map(|(documented_method, _)| {
+            std::ptr::eq(&documented_method.item, function)
+                .then_some(documented_method.doc_comments.as_slice())
+        });
 
-        let names_to_exclude = names_to_exclude(function, name);
+        let param = FunctionParam { function, parameter, name, doc_comments };
+        self.complete_function_param(functions, param, FunctionParamScope::TypeImpl);
+        true
+    }
 
-        self.suggest_function_parameters(functions, name, names_to_exclude);
+    pub(super) fn try_complete_function_param_in_trait_impl(
+        &mut self,
+        noir_trait_impl: &NoirTraitImpl,
+    ) -> bool {
+        let functions = noir_trait_impl.items.iter().filter_map(|item| {
+            if let TraitImplItemKind::Function(function) = &item.item.kind {
+                Some(function)
+            } else {
+                None
+            }
+        });
 
+        let function_and_parameter =
+            find_function_and_parameter_at_byte_index(functions.clone(), self.byte_index);
+        let Some((function, parameter, name)) = function_and_parameter else {
+            return self.try_complete_function_param_type(function_parameters(functions));
+        };
+        let doc_comments = noir_trait_impl.items.iter().find_map(|item| match &item.item.kind {
+            TraitImplItemKind::Function(item_function) if std::ptr::eq(item_function, function) => {
+                Some(item.doc_comments.as_slice())
+            }
+            _ => None,
+        });
+
+        let param = FunctionParam { function, parameter, name, doc_comments };
+        let scope = FunctionParamScope::TraitImpl(noir_trait_impl);
+        self.complete_function_param(functions, param, scope);
         true
     }
 
@@ -56,16 +201,25 @@ impl NodeFinder<'_> {
         // Since NoirTrait doesn't hold `NoirFunction`s we have to repeat a bit the code here.
         let parameters_and_name = trait_.items.iter().find_map(|documented_item| {
             if let TraitItem::Function { parameters, .. } = &documented_item.item {
-                for (name, _typ) in parameters {
-                    if self.byte_index == name.span().end() as usize {
-                        return Some((parameters, name.as_str()));
+                for (index, (name, typ)) in parameters.iter().enumerate() {
+                    if self.byte_index == ident_end(name) {
+                        let doc_comments = documented_item.doc_comments.as_slice();
+                        return Some((parameters, index, name.as_str(), typ, doc_comments));
                     }
                 }
             }
             None
         });
-        let Some((parameters, name)) = parameters_and_name else {
-            return false;
+        let Some((parameters, index, name, current_typ, doc_comments)) = parameters_and_name else {
+            let parameters = trait_.items.iter().flat_map(|documented_item| {
+                if let TraitItem::Function { parameters, .. } = &documented_item.item {
+                    parameters.as_slice()
+                } else {
+                    &[]
+                }
+            });
+            return self
+                .try_complete_function_param_type(parameters.map(|(ident, typ)| (ident, typ)));
         };
 
         let mut names_to_exclude = HashSet::new();
@@ -75,40 +229,207 @@ impl NodeFinder<'_> {
             }
         }
 
-        let mut suggested = HashSet::new();
+        if index == 0 {
+            self.suggest_self_receivers(name);
+        }
+        let other_names = parameters.iter().map(|(ident, _)| ident.as_str());
+        let convention = NamingConvention::detect(other_names.filter(|ident| *ident != name));
+        self.suggest_parameter_names_for_type(current_typ, name, &names_to_exclude, convention);
+
+        let first_parameter_item = self.completion_items.len();
+        let mut candidates = Vec::new();
+        let mut budget = self.parameter_search_budget();
         for documented_item in &trait_.items {
             if let TraitItem::Function { parameters, .. } = &documented_item.item {
+                if !budget.take_function() {
+                    break;
+                }
                 for (ident, typ) in parameters {
-                    if matches!(typ.typ, UnresolvedTypeData::Error) {
-                        continue;
-                    };
-                    let param_name = ident.as_str();
-                    if names_to_exclude.contains(param_name) {
-                        continue;
-                    }
-
-                    if name_matches(param_name, name) {
-                        let label = format!("{param_name}: {typ}");
-                        if suggested.insert(label.clone()) {
-                            let item = variable_completion_item(label, None);
-                            self.completion_items.push(item);
-                        }
+                    if !matches!(typ.typ, UnresolvedTypeData::Error) {
+                        candidates.push((ident.to_string(), render_param_type(typ)));
                     }
                 }
             }
         }
+        let mut suggested = HashSet::new();
+        self.suggest_parameter_candidates(
+            candidates,
+            name,
+            current_typ,
+            &names_to_exclude,
+            &mut suggested,
+        );
+        self.suggest_documented_parameter_names(
+            doc_comments,
+            name,
+            &names_to_exclude,
+            &mut suggested,
+        );
+        self.note_parameter_count_over_threshold(
+            parameters.len(),
+            current_typ,
+            first_parameter_item,
+        );
 
         true
     }
 
+    /// Suggests completions for `param`, mostly taken from the parameters of `functions`, the
+    /// functions defined next to it in `scope`.
+    fn complete_function_param<'a>(
+        &mut self,
+        functions: impl Iterator<Item = &'a NoirFunction> + Clone,
+        param: FunctionParam<'_>,
+        scope: FunctionParamScope<'_>,
+    ) {
+        let FunctionParam { function, parameter, name, doc_comments } = param;
+        let names_to_exclude = names_to_exclude(function, name);
+
+        if !matches!(scope, FunctionParamScope::Module) && is_first_parameter(function, parameter) {
+            self.suggest_self_receivers(name);
+        }
+        let convention = function_naming_convention(function, name);
+        self.suggest_parameter_names_for_type(&parameter.typ, name, &names_to_exclude, convention);
+        let first_parameter_item = self.completion_items.len();
+        // Methods overridden in a trait impl usually repeat the parameters of the trait's default
+        // implementation, so both share `suggested` to only suggest those once.
+        let mut suggested = HashSet::new();
+        self.suggest_function_parameters(
+            functions.clone(),
+            name,
+            &parameter.typ,
+            &names_to_exclude,
+            &mut suggested,
+        );
+        if let FunctionParamScope::TraitImpl(noir_trait_impl) = scope {
+            let default_methods = trait_default_methods(self.interner, noir_trait_impl);
+            let (candidates, _) = self.collect_parameter_candidates(default_methods);
+            self.suggest_parameter_candidates(
+                candidates,
+                name,
+                &parameter.typ,
+                &names_to_exclude,
+                &mut suggested,
+            );
+        }
+        self.suggest_generic_instantiation_hints(
+            functions.clone(),
+            function,
+            name,
+            &names_to_exclude,
+            &mut suggested,
+        );
+        self.suggest_remaining_parameters(functions.clone(), function, parameter, name);
+        if let FunctionParamScope::Module = scope {
+            let parsed_function_names: HashSet<&str> =
+                functions.map(|function| function.name()).collect();
+            self.suggest_expanded_function_parameters(
+                &parsed_function_names,
+                name,
+                &parameter.typ,
+                &names_to_exclude,
+                &mut suggested,
+            );
+        }
+        self.flag_parameters_conflicting_with_body(function, first_parameter_item);
+        self.suggest_documented_parameter_names(
+            doc_comments.unwrap_or_default(),
+            name,
+            &names_to_exclude,
+            &mut suggested,
+        );
+        self.suggest_free_identifiers_in_body(function, name, &names_to_exclude, &suggested);
+        self.note_parameter_count_over_threshold(
+            function.parameters().len(),
+            &parameter.typ,
+            first_parameter_item,
+        );
+        self.suggest_unused_parameter_placeholder(function, parameter, name);
+    }
+
+    /// Suggests the parameters of `functions` whose name matches `name`.
+    ///
+    /// The parameters are cached until the file changes, so that completing a parameter again
+    /// in the same scope doesn't need to look through every function again.
     fn suggest_function_parameters<'a>(
         &mut self,
         functions: impl Iterator<Item = &'a NoirFunction>,
         name: &str,
-        names_to_exclude: HashSet<String>,
+        current_typ: &UnresolvedType,
+        names_to_exclude: &HashSet<String>,
+        suggested: &mut HashSet<String>,
     ) {
-        let mut suggested = HashSet::new();
+        let mut functions = functions.peekable();
+        let Some(scope_start) = functions.peek().map(|function| function.location().span.start())
+        else {
+            return;
+        };
+
+        let candidates = if let Some(candidates) = self.parameter_candidates.get(&scope_start) {
+            candidates.clone()
+        } else {
+            let (candidates, complete) = self.collect_parameter_candidates(functions);
+            if complete {
+                self.parameter_candidates.insert(scope_start, candidates.clone());
+            }
+            candidates
+        };
+        self.suggest_parameter_candidates(
+            candidates,
+            name,
+            current_typ,
+            names_to_exclude,
+            suggested,
+        );
+    }
+
+    /// Suggests the `(name, type)` parameter `candidates` whose name matches `name`.
+    ///
+    /// If some candidates take a struct-like type by reference, the candidates that take one by
+    /// value are suggested in their borrowed form too. Without type information it's unknown
+    /// whether the type is cheap to copy, so both forms are offered.
+    fn suggest_parameter_candidates(
+        &mut self,
+        candidates: Vec<(String, String)>,
+        name: &str,
+        current_typ: &UnresolvedType,
+        names_to_exclude: &HashSet<String>,
+        suggested: &mut HashSet<String>,
+    ) {
+        let borrows_by_convention =
+            candidates.iter().any(|(_, typ)| typ.strip_prefix('&').is_some_and(is_borrowable_type));
+
+        for (param_name, typ) in candidates {
+            if names_to_exclude.contains(&param_name)
+                || suggestion_adds_nothing(&param_name, name, current_typ)
+            {
+                continue;
+            }
+
+            if name_matches(&param_name, name) {
+                let borrowed_typ =
+                    (borrows_by_convention && is_borrowable_type(&typ)).then(|| format!("&{typ}"));
+                self.suggest_parameter(&param_name, typ, name, current_typ, suggested);
+                if let Some(borrowed_typ) = borrowed_typ {
+                    self.suggest_parameter(&param_name, borrowed_typ, name, current_typ, suggested);
+                }
+            }
+        }
+    }
+
+    /// Returns the names and types of the parameters of `functions` that are identifiers with a type,
+    /// in source order, and whether all of `functions` were looked at before the search stopped.
+    fn collect_parameter_candidates<'a>(
+        &self,
+        functions: impl Iterator<Item = &'a NoirFunction>,
+    ) -> (Vec<(String, String)>, bool) {
+        let mut candidates = Vec::new();
+        let mut budget = self.parameter_search_budget();
         for function in functions {
+            if !budget.take_function() {
+                return (candidates, false);
+            }
+
             for parameter in function.parameters() {
                 let Pattern::Identifier(ident) = &parameter.pattern else {
                     continue;
@@ -116,44 +437,935 @@ impl NodeFinder<'_> {
                 if matches!(parameter.typ.typ, UnresolvedTypeData::Error) {
                     continue;
                 };
-                let param_name = ident.as_str();
-                if names_to_exclude.contains(param_name) {
+                candidates.push((ident.to_string(), render_param_type(&parameter.typ)));
+            }
+        }
+        (candidates, true)
+    }
+
+    /// If the `genericInstantiationHints` option is enabled, suggests the parameters of `functions`
+    /// whose type uses type parameters that `function` doesn't have, with those type parameters
+    /// replaced by the concrete type that all of `function`'s other parameters have (if there's one).
+    /// As that type is only a guess, these suggestions are marked as hints.
+    fn suggest_generic_instantiation_hints<'a>(
+        &mut self,
+        functions: impl Iterator<Item = &'a NoirFunction>,
+        function: &NoirFunction,
+        name: &str,
+        names_to_exclude: &HashSet<String>,
+        suggested: &mut HashSet<String>,
+    ) {
+        if !self.options.generic_instantiation_hints {
+            return;
+        }
+        let Some(concrete_type) = single_concrete_parameter_type(function) else {
+            return;
+        };
+        let own_type_parameters = type_parameter_names(function);
+
+        let mut budget = self.parameter_search_budget();
+        for other_function in functions {
+            if !budget.take_function() {
+                return;
+            }
+
+            let type_parameters = type_parameter_names(other_function);
+            let type_parameters: HashSet<_> =
+                type_parameters.difference(&own_type_parameters).copied().collect();
+            if type_parameters.is_empty() {
+                continue;
+            }
+
+            for parameter in other_function.parameters() {
+                let Some(param_name) = parameter_name(parameter) else {
+                    continue;
+                };
+                if matches!(parameter.typ.typ, UnresolvedTypeData::Error)
+                    || names_to_exclude.contains(param_name)
+                    || !name_matches(param_name, name)
+                {
                     continue;
                 }
 
-                if name_matches(param_name, name) {
-                    let label = format!("{param_name}: {}", parameter.typ);
-                    if suggested.insert(label.clone()) {
-                        let item = variable_completion_item(label, None);
-                        self.completion_items.push(item);
+                let typ = render_param_type(&parameter.typ);
+                let instantiated_typ =
+                    instantiate_type_parameters(&typ, &type_parameters, &concrete_type);
+                if instantiated_typ == typ {
+                    continue;
+                }
+
+                let label = format!("{param_name}: {instantiated_typ}");
+                if suggested.insert(label.clone()) {
+                    let item = variable_completion_item(label, Some("hint".to_string()));
+                    let detail =
+                        format!("Hint: `{typ}` with its type parameters set to `{concrete_type}`");
+                    let item = completion_item_with_detail(item, detail);
+                    self.push_parameter_item(item, param_name, instantiated_typ);
+                }
+            }
+        }
+    }
+
+    /// If `parameter` is the last, still untyped, parameter of `function` and the parameters before it
+    /// are the first parameters of another function, suggests a snippet that fills in all of that
+    /// function's parameters from `parameter` onwards.
+    fn suggest_remaining_parameters<'a>(
+        &mut self,
+        functions: impl Iterator<Item = &'a NoirFunction>,
+        function: &NoirFunction,
+        parameter: &Param,
+        name: &str,
+    ) {
+        if !is_last_parameter(function, parameter)
+            || !matches!(parameter.typ.typ, UnresolvedTypeData::Error)
+        {
+            return;
+        }
+
+        let written_names = vecmap(function.parameters(), parameter_name);
+        let previous_names = &written_names[..written_names.len() - 1];
+
+        let mut suggested = HashSet::new();
+        let mut budget = self.parameter_search_budget();
+        for other_function in functions {
+            if !budget.take_function() {
+                return;
+            }
+            if std::ptr::eq(other_function, function) {
+                continue;
+            }
+
+            // Filling in a single parameter is already covered by the regular suggestions
+            let other_parameters = other_function.parameters();
+            if other_parameters.len() < previous_names.len() + 2 {
+                continue;
+            }
+
+            let other_names = vecmap(other_parameters, parameter_name);
+            if other_names[..previous_names.len()] != *previous_names {
+                continue;
+            }
+            if !other_names[previous_names.len()].is_some_and(|other| name_matches(other, name)) {
+                continue;
+            }
+
+            // Every remaining parameter must be a plain identifier with a type
+            let remaining_parameters = other_parameters[previous_names.len()..].iter();
+            let remaining_names = other_names[previous_names.len()..].iter();
+            let remaining: Option<Vec<_>> = remaining_parameters
+                .zip(remaining_names)
+                .map(|(other_parameter, param_name)| {
+                    if matches!(other_parameter.typ.typ, UnresolvedTypeData::Error) {
+                        None
+                    } else {
+                        Some(((*param_name)?, render_param_type(&other_parameter.typ)))
                     }
+                })
+                .collect();
+            let Some(remaining) = remaining else {
+                continue;
+            };
+
+            let labels = vecmap(&remaining, |(param_name, typ)| format!("{param_name}: {typ}"));
+            let snippets = remaining.iter().enumerate().map(|(index, (param_name, typ))| {
+                format!("${{{}:{param_name}}}: {typ}", index + 1)
+            });
+            let snippets: Vec<_> = snippets.collect();
+
+            let label = labels.join(", ");
+            if suggested.insert(label.clone()) {
+                let mut item = snippet_completion_item(
+                    label,
+                    CompletionItemKind::SNIPPET,
+                    snippets.join(", "),
+                    Some("fill remaining parameters".to_string()),
+                );
+                item.detail = self.parameter_count_note(previous_names.len() + remaining.len());
+                self.completion_items.push(item);
+            }
+        }
+    }
+
+    /// If the cursor is right after the colon of a parameter (like in `x:`), suggests the types that
+    /// parameters with the same name have in the same module, impl or trait.
+    /// Returns false if the cursor isn't there or there's nothing to suggest.
+    fn try_complete_function_param_type<'b>(
+        &mut self,
+        parameters: impl Iterator<Item = (&'b Ident, &'b UnresolvedType)> + Clone,
+    ) -> bool {
+        let byte_index = self.byte_index;
+        let source = self.source;
+        let parameter_before_cursor = parameters.clone().find(|(ident, _)| {
+            let end = ident_end(ident);
+            end < byte_index && source.get(end..byte_index).is_some_and(|text| text.trim() == ":")
+        });
+        let Some((name, _)) = parameter_before_cursor else {
+            return false;
+        };
+
+        let mut suggested = HashSet::new();
+        for (ident, typ) in parameters {
+            if ident.as_str() != name.as_str() || matches!(typ.typ, UnresolvedTypeData::Error) {
+                continue;
+            }
+
+            let typ = render_param_type(typ);
+            if suggested.insert(typ.clone()) {
+                let item = simple_completion_item(typ, CompletionItemKind::STRUCT, None);
+                self.completion_items.push(item);
+            }
+        }
+        !suggested.is_empty()
+    }
+
+    /// Suggests parameters of the current module's functions that aren't part of the parsed source,
+    /// for example because an attribute macro generated them.
+    fn suggest_expanded_function_parameters(
+        &mut self,
+        parsed_function_names: &HashSet<&str>,
+        name: &str,
+        current_typ: &UnresolvedType,
+        names_to_exclude: &HashSet<String>,
+        suggested: &mut HashSet<String>,
+    ) {
+        let interner = self.interner;
+        let def_map = &self.def_maps[&self.module_id.krate];
+        let Some(module_data) = def_map.get(self.module_id.local_id) else {
+            return;
+        };
+
+        // Definitions are stored by name, so sort them back into source order
+        let mut func_metas: Vec<_> = module_data
+            .value_definitions()
+            .filter_map(|module_def_id| {
+                let ModuleDefId::FunctionId(func_id) = module_def_id else {
+                    return None;
+                };
+                if parsed_function_names.contains(interner.function_name(&func_id)) {
+                    return None;
+                }
+                interner.try_function_meta(&func_id)
+            })
+            .collect();
+        func_metas.sort_by_key(|func_meta| func_meta.location.span.start());
+
+        let mut budget = self.parameter_search_budget();
+        for func_meta in func_metas {
+            if !budget.take_function() {
+                return;
+            }
+
+            for (pattern, typ, _) in func_meta.parameters.iter() {
+                let HirPattern::Identifier(ident) = pattern else {
+                    continue;
+                };
+                let param_name = interner.definition_name(ident.id);
+                if names_to_exclude.contains(param_name)
+                    || suggestion_adds_nothing(param_name, name, current_typ)
+                {
+                    continue;
+                }
+
+                if name_matches(param_name, name) {
+                    let typ = typ.to_string();
+                    self.suggest_parameter(param_name, typ, name, current_typ, suggested);
                 }
             }
         }
     }
+
+    /// Suggests `param_name: typ`, unless that label was already `suggested`.
+    ///
+    /// If the parameter being completed already has a type, suggestions are ranked by whether
+    /// their type is the same and by how well their name matches, in the order given by the
+    /// `parameterRanking` option. Equally ranked suggestions, and all of them if there's no type
+    /// yet, keep the order they were found in.
+    ///
+    /// Types longer than the `maxParameterTypeLength` option are cut off in the label, but are still
+    /// inserted in full and shown in the suggestion's detail.
+    fn suggest_parameter(
+        &mut self,
+        param_name: &str,
+        typ: String,
+        name: &str,
+        current_typ: &UnresolvedType,
+        suggested: &mut HashSet<String>,
+    ) {
+        let label = format!("{param_name}: {typ}");
+        let discovery_index = suggested.len();
+        if !suggested.insert(label.clone()) {
+            return;
+        }
+
+        let max_type_length = self.options.max_parameter_type_length;
+        let mut item = if typ.chars().count() > max_type_length {
+            let truncated_typ: String = typ.chars().take(max_type_length).collect();
+            let mut item =
+                variable_completion_item(format!("{param_name}: {truncated_typ}…"), None);
+            item.insert_text = Some(label);
+            item.detail = Some(typ.clone());
+            item
+        } else {
+            variable_completion_item(label, None)
+        };
+        let ranks = if matches!(current_typ.typ, UnresolvedTypeData::Error) {
+            (0, 0)
+        } else {
+            let type_rank = usize::from(typ != render_param_type(current_typ));
+            let name_rank = usize::from(!param_name.starts_with(name));
+            match self.options.parameter_ranking {
+                ParameterRanking::TypeFirst => (type_rank, name_rank),
+                ParameterRanking::NameFirst => (name_rank, type_rank),
+            }
+        };
+        let sort_text = ranked_parameter_sort_text(ranks, discovery_index);
+        item = completion_item_with_sort_text(item, sort_text);
+        self.push_parameter_item(item, param_name, typ);
+    }
+
+    /// Adds a `name: Type` parameter suggestion, remembering its name and full type.
+    fn push_parameter_item(&mut self, item: CompletionItem, param_name: &str, typ: String) {
+        let index = self.completion_items.len();
+        self.suggested_parameter_types.insert(index, (param_name.to_string(), typ));
+        self.completion_items.push(item);
+    }
+
+    /// Marks the `name: Type` suggestions from `first_item` onwards whose name `function`'s body uses
+    /// at a different type as deprecated (which editors usually show by striking them through),
+    /// explaining why at the end of their detail.
+    fn flag_parameters_conflicting_with_body(
+        &mut self,
+        function: &NoirFunction,
+        first_item: usize,
+    ) {
+        let mut collector = BodyTypeHintCollector::default();
+        function.def.body.accept(None, &mut collector);
+        if collector.types.is_empty() {
+            return;
+        }
+
+        for (index, item) in self.completion_items.iter_mut().enumerate().skip(first_item) {
+            let Some((param_name, typ)) = self.suggested_parameter_types.get(&index) else {
+                continue;
+            };
+            let Some(used_type) = collector.types.get(param_name) else {
+                continue;
+            };
+            if used_type != typ {
+                let note = format!("`{param_name}` is used as `{used_type}` in the function body");
+                append_to_detail(item, note);
+                item.tags = Some(vec![CompletionItemTag::DEPRECATED]);
+            }
+        }
+    }
+
+    /// Suggests the parameter names mentioned in `doc_comments` that weren't `suggested` yet,
+    /// adding them to `suggested`.
+    fn suggest_documented_parameter_names(
+        &mut self,
+        doc_comments: &[DocComment],
+        name: &str,
+        names_to_exclude: &HashSet<String>,
+        suggested: &mut HashSet<String>,
+    ) {
+        for param_name in documented_parameter_names(doc_comments) {
+            if param_name == name
+                || names_to_exclude.contains(param_name)
+                || !name_matches(param_name, name)
+            {
+                continue;
+            }
+
+            let already_suggested = suggested.iter().any(|label| {
+                label.split_once(':').map_or(label.as_str(), |(param, _)| param) == param_name
+            });
+            if !already_suggested {
+                suggested.insert(param_name.to_string());
+                self.completion_items.push(variable_completion_item(param_name, None));
+            }
+        }
+    }
+
+    /// Suggests identifiers that `function`'s body uses without defining them, as these are
+    /// likely meant to be parameters that weren't declared yet.
+    fn suggest_free_identifiers_in_body(
+        &mut self,
+        function: &NoirFunction,
+        name: &str,
+        names_to_exclude: &HashSet<String>,
+        suggested: &HashSet<String>,
+    ) {
+        let mut collector = FreeIdentifierCollector::default();
+        function.def.body.accept(None, &mut collector);
+
+        let def_map = &self.def_maps[&self.module_id.krate];
+        let module_values =
+            def_map.get(self.module_id.local_id).map(|module| module.scope().values());
+
+        for (identifier, location) in collector.used {
+            if collector.bound.contains(&identifier)
+                || collector.called.contains(&identifier)
+                || identifier == name
+                || names_to_exclude.contains(&identifier)
+                || !name_matches(&identifier, name)
+            {
+                continue;
+            }
+
+            // Skip names that resolve to something in the function's scope, like globals and
+            // functions defined in or imported into the module, and names that were already suggested
+            let resolved = self.interner.find_referenced(location).is_some();
+            let defined_in_module = module_values
+                .is_some_and(|values| values.keys().any(|ident| ident.as_str() == identifier));
+            let already_suggested = suggested.iter().any(|label| {
+                label.split_once(':').map_or(label.as_str(), |(param, _)| param) == identifier
+            });
+            if resolved || defined_in_module || already_suggested {
+                continue;
+            }
+
+            self.completion_items.push(variable_completion_item(identifier, None));
+        }
+    }
+
+    /// If the parameter being completed doesn't have a type yet (so accepting a suggestion adds it)
+    /// and that makes the function have more parameters than the `maxParameters` option allows,
+    /// adds a note about it to the detail of the `name: Type` suggestions from `first_item` onwards.
+    fn note_parameter_count_over_threshold(
+        &mut self,
+        parameter_count: usize,
+        current_typ: &UnresolvedType,
+        first_item: usize,
+    ) {
+        if !matches!(current_typ.typ, UnresolvedTypeData::Error) {
+            return;
+        }
+        let Some(note) = self.parameter_count_note(parameter_count) else {
+            return;
+        };
+
+        for item in &mut self.completion_items[first_item..] {
+            if item.kind != Some(CompletionItemKind::VALUE) {
+                continue;
+            }
+            append_to_detail(item, note.clone());
+        }
+    }
+
+    fn parameter_count_note(&self, parameter_count: usize) -> Option<String> {
+        let max_parameters = self.options.max_parameters;
+        (parameter_count > max_parameters).then(|| {
+            format!("More than {max_parameters} parameters, consider grouping them into a struct")
+        })
+    }
+
+    /// Returns the budget for a new pass of the search for parameters to suggest.
+    fn parameter_search_budget(&self) -> ParameterSearchBudget {
+        ParameterSearchBudget {
+            remaining_functions: self.options.max_parameter_search_functions,
+            cancellation: self.cancellation.clone(),
+        }
+    }
+
+    /// If `parameter` already has a type but `function`'s (non-empty) body never uses it,
+    /// suggests prefixing its name with an underscore to mark it as intentionally unused.
+    fn suggest_unused_parameter_placeholder(
+        &mut self,
+        function: &NoirFunction,
+        parameter: &Param,
+        name: &str,
+    ) {
+        if name.starts_with('_')
+            || matches!(parameter.typ.typ, UnresolvedTypeData::Error)
+            || function.def.body.is_empty()
+        {
+            return;
+        }
+
+        let mut collector = FreeIdentifierCollector::default();
+        function.def.body.accept(None, &mut collector);
+        if !collector.used.iter().any(|(used, _)| used == name) {
+            self.completion_items.push(variable_completion_item(format!("_{name}"), None));
+        }
+    }
+
+    /// Suggests `self`, `&self` and `&mut self`, sorted before any other suggestion.
+    fn suggest_self_receivers(&mut self, name: &str) {
+        if !name_matches("self", name) {
+            return;
+        }
+
+        for receiver in ["self", "&self", "&mut self"] {
+            if receiver == name {
+                continue;
+            }
+
+            let item = simple_completion_item(receiver, CompletionItemKind::KEYWORD, None);
+            let item = completion_item_with_sort_text(item, self_receiver_sort_text());
+            self.completion_items.push(item);
+        }
+    }
+
+    /// Suggests names that are commonly used for parameters of the given type.
+    /// Because the type is already written, only the name is suggested.
+    fn suggest_parameter_names_for_type(
+        &mut self,
+        typ: &UnresolvedType,
+        name: &str,
+        names_to_exclude: &HashSet<String>,
+        convention: Option<NamingConvention>,
+    ) {
+        let numeric_names = self.options.numeric_parameter_names;
+        let common_names = parameter_names_for_type(&typ.typ, numeric_names).iter();
+        let common_names = common_names.map(|param_name| param_name.to_string());
+        let style = self.options.parameter_name_style;
+        let type_name = parameter_name_from_type_name(&typ.typ, style);
+
+        for param_name in common_names.chain(type_name) {
+            let param_name = match &convention {
+                Some(convention) => convention.apply(param_name),
+                None => param_name,
+            };
+            if names_to_exclude.contains(&param_name)
+                || suggestion_adds_nothing(&param_name, name, typ)
+            {
+                continue;
+            }
+
+            if name_matches(&param_name, name) {
+                let item = variable_completion_item(param_name, None);
+                self.completion_items.push(item);
+            }
+        }
+    }
+}
+
+/// A naming convention followed by all of a function's other parameters, which the names
+/// suggested for a type are adjusted to.
+enum NamingConvention {
+    /// All names start with this prefix, like `in_` for `in_a` and `in_b`.
+    Prefix(String),
+    /// All names end with this suffix, like `_in` for `a_in` and `b_in`.
+    Suffix(String),
+}
+
+impl NamingConvention {
+    /// Detects the convention that all `names` follow, if any. Only prefixes up to the first
+    /// underscore and suffixes from the last one are considered, and at least two names are
+    /// needed so that a single parameter doesn't impose its name on the others.
+    fn detect<'a>(names: impl Iterator<Item = &'a str>) -> Option<Self> {
+        let names: Vec<&str> = names.collect();
+        let [first, _, ..] = names.as_slice() else {
+            return None;
+        };
+
+        let prefix = first.find('_').filter(|index| *index > 0).map(|index| &first[..=index]);
+        if let Some(prefix) = prefix {
+            if names.iter().all(|name| name.len() > prefix.len() && name.starts_with(prefix)) {
+                return Some(NamingConvention::Prefix(prefix.to_string()));
+            }
+        }
+
+        let suffix = first.rfind('_').filter(|index| *index > 0).map(|index| &first[index..]);
+        let suffix = suffix.filter(|suffix| suffix.len() > 1)?;
+        names
+            .iter()
+            .all(|name| name.len() > suffix.len() && name.ends_with(suffix))
+            .then(|| NamingConvention::Suffix(suffix.to_string()))
+    }
+
+    /// Returns `name` adjusted to follow this convention.
+    fn apply(&self, name: String) -> String {
+        match self {
+            NamingConvention::Prefix(prefix) if !name.starts_with(prefix.as_str()) => {
+                format!("{prefix}{name}")
+            }
+            NamingConvention::Suffix(suffix) if !name.ends_with(suffix.as_str()) => {
+                format!("{name}{suffix}")
+            }
+            _ => name,
+        }
+    }
+}
+
+/// Collects the single-segment variables used in a function body, in order of appearance,
+/// the names bound by patterns and `for` loops inside it and the names it calls.
+///
+/// Scopes aren't tracked, so a name bound anywhere in the body is considered bound everywhere.
+#[derive(Default)]
+struct FreeIdentifierCollector {
+    /// Single-segment names used in the body, with the location of their first use.
+    used: Vec<(String, Location)>,
+    bound: HashSet<String>,
+    /// Single-segment names used as call targets, which are most likely functions (like
+    /// `println`) rather than parameters.
+    called: HashSet<String>,
+}
+
+impl FreeIdentifierCollector {
+    fn use_path(&mut self, path: &Path) {
+        let Some(ident) = path.as_ident() else {
+            return;
+        };
+        if ident.as_str() != "self" && !self.used.iter().any(|(used, _)| used == ident.as_str()) {
+            self.used.push((ident.to_string(), ident.location()));
+        }
+    }
+}
+
+impl Visitor for FreeIdentifierCollector {
+    fn visit_variable(&mut self, path: &Path, _: Span) -> bool {
+        self.use_path(path);
+        true
+    }
+
+    fn visit_lvalue_path(&mut self, path: &Path) {
+        self.use_path(path);
+    }
+
+    fn visit_identifier_pattern(&mut self, ident: &Ident) {
+        self.bound.insert(ident.to_string());
+    }
+
+    fn visit_for_loop_statement(&mut self, for_loop: &ForLoopStatement) -> bool {
+        self.bound.insert(for_loop.identifier.to_string());
+        true
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression, _: Span) -> bool {
+        if let ExpressionKind::Variable(path) = &call.func.kind {
+            if let Some(ident) = path.as_ident() {
+                self.called.insert(ident.to_string());
+            }
+        }
+        true
+    }
+}
+
+/// Returns the parameter names that doc comments mention, in order of appearance.
+///
+/// A name is mentioned either in an `@param name ...` tag or at the start of a line followed
+/// by a colon (like `amount: the value`), optionally in backticks and in a list item.
+fn documented_parameter_names(doc_comments: &[DocComment]) -> Vec<&str> {
+    let mut names = Vec::new();
+    for doc_comment in doc_comments {
+        for line in doc_comment.contents.lines() {
+            let line = line.trim().trim_start_matches(['-', '*']).trim_start();
+            let mentioned_name = if let Some(rest) = line.strip_prefix("@param ") {
+                rest.split_whitespace().next()
+            } else {
+                line.split_once(':').map(|(before_colon, _)| before_colon.trim())
+            };
+            let Some(mentioned_name) = mentioned_name.map(|name| name.trim_matches('`')) else {
+                continue;
+            };
+            if looks_like_parameter_name(mentioned_name) && !names.contains(&mentioned_name) {
+                names.push(mentioned_name);
+            }
+        }
+    }
+    names
+}
+
+/// Returns true for snake case identifiers like `amount` or `max_value2`, which excludes
+/// capitalized words that commonly start a line like `Note:`.
+fn looks_like_parameter_name(name: &str) -> bool {
+    let is_valid_char = |c: char| c.is_ascii_lowercase() || c.is_numeric() || c == '_';
+    name.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+        && name.chars().all(is_valid_char)
+}
+
+/// Collects the types that a function body evidently expects some single-segment variables to have:
+/// the annotated type of a `let` that is assigned just that variable, and `bool` for a variable
+/// used as an `if` condition. Only the first type found for each variable is kept.
+#[derive(Default)]
+struct BodyTypeHintCollector {
+    types: HashMap<String, String>,
+}
+
+impl BodyTypeHintCollector {
+    fn add_type_hint(&mut self, expression_kind: &ExpressionKind, typ: String) {
+        if let ExpressionKind::Variable(path) = expression_kind {
+            if let Some(ident) = path.as_ident() {
+                self.types.entry(ident.to_string()).or_insert(typ);
+            }
+        }
+    }
+}
+
+impl Visitor for BodyTypeHintCollector {
+    fn visit_let_statement(&mut self, let_statement: &LetStatement) -> bool {
+        if let Some(typ) = &let_statement.r#type {
+            if !matches!(typ.typ, UnresolvedTypeData::Error) {
+                self.add_type_hint(&let_statement.expression.kind, render_param_type(typ));
+            }
+        }
+        true
+    }
+
+    fn visit_if_expression(&mut self, if_expression: &IfExpression, _: Span) -> bool {
+        self.add_type_hint(&if_expression.condition.kind, "bool".to_string());
+        true
+    }
 }
 
 /// Tries to find a function parameter inside `functions` that is being autocompleted.
-/// Returns that function together with the parameter name, if found.
-fn find_function_and_parameter_name_at_byte_index<'a>(
+/// Returns that function together with the parameter and its name, if found.
+fn find_function_and_parameter_at_byte_index<'a>(
     mut functions: impl Iterator<Item = &'a NoirFunction>,
     byte_index: usize,
-) -> Option<(&'a NoirFunction, &'a str)> {
+) -> Option<(&'a NoirFunction, &'a Param, &'a str)> {
     functions.find_map(|function| {
         for parameter in function.parameters() {
             let Pattern::Identifier(ident) = &parameter.pattern else {
                 return None;
             };
-            if byte_index == ident.span().end() as usize {
-                return Some((function, ident.as_str()));
+            if byte_index == ident_end(ident) {
+                return Some((function, parameter, ident.as_str()));
             }
         }
         None
     })
 }
 
+/// Returns the byte index right after `ident`.
+///
+/// Identifier spans end one byte after the start of their last character, which falls short of
+/// the actual end when that character is encoded with more than one byte (identifiers may contain
+/// non-ASCII numeric characters, like `x²`), so the end is computed from the name's length instead.
+fn ident_end(ident: &Ident) -> usize {
+    ident.span().start() as usize + ident.as_str().len()
+}
+
+/// Returns the name and type of every parameter of `functions` that is a plain identifier.
+fn function_parameters<'a>(
+    functions: impl Iterator<Item = &'a NoirFunction> + Clone,
+) -> impl Iterator<Item = (&'a Ident, &'a UnresolvedType)> + Clone {
+    functions.flat_map(|function| {
+        function.parameters().iter().filter_map(|parameter| match &parameter.pattern {
+            Pattern::Identifier(ident) => Some((ident, &parameter.typ)),
+            _ => None,
+        })
+    })
+}
+
+fn is_first_parameter(function: &NoirFunction, parameter: &Param) -> bool {
+    function.parameters().first().is_some_and(|first| first.location == parameter.location)
+}
+
+fn is_last_parameter(function: &NoirFunction, parameter: &Param) -> bool {
+    function.parameters().last().is_some_and(|last| last.location == parameter.location)
+}
+
+/// Returns the name of a parameter whose pattern is a plain identifier.
+fn parameter_name(parameter: &Param) -> Option<&str> {
+    if let Pattern::Identifier(ident) = &parameter.pattern { Some(ident.as_str()) } else { None }
+}
+
+/// Returns the naming convention of `function`'s parameters other than `name`.
+fn function_naming_convention(function: &NoirFunction, name: &str) -> Option<NamingConvention> {
+    let other_names = function.parameters().iter().filter_map(parameter_name);
+    NamingConvention::detect(other_names.filter(|param_name| *param_name != name))
+}
+
+/// Returns the default implementations of the methods of the trait that `noir_trait_impl` implements.
+fn trait_default_methods<'a>(
+    interner: &'a NodeInterner,
+    noir_trait_impl: &NoirTraitImpl,
+) -> impl Iterator<Item = &'a NoirFunction> {
+    // The trait is referenced at the last segment of its path, not where the path starts
+    let location = match &noir_trait_impl.r#trait.typ {
+        UnresolvedTypeData::Named(path, ..) => path.last_ident().location(),
+        _ => noir_trait_impl.r#trait.location,
+    };
+    let trait_id = match interner.find_referenced(location) {
+        Some(ReferenceId::Trait(trait_id)) => Some(trait_id),
+        _ => None,
+    };
+    let methods = trait_id.map(|trait_id| &interner.get_trait(trait_id).methods);
+    methods.into_iter().flatten().filter_map(|method| method.default_impl.as_deref())
+}
+
+/// Returns the names of `function`'s type parameters (numeric generics aren't included).
+fn type_parameter_names(function: &NoirFunction) -> HashSet<&str> {
+    function
+        .def
+        .generics
+        .iter()
+        .filter_map(|generic| match generic {
+            UnresolvedGeneric::Variable(IdentOrQuotedType::Ident(ident), _) => Some(ident.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the type of `function`'s typed parameters if they all have the same one and it doesn't
+/// use any of the function's type parameters.
+fn single_concrete_parameter_type(function: &NoirFunction) -> Option<String> {
+    let type_parameters = type_parameter_names(function);
+    let mut types = function
+        .parameters()
+        .iter()
+        .filter(|parameter| !matches!(parameter.typ.typ, UnresolvedTypeData::Error))
+        .map(|parameter| render_param_type(&parameter.typ));
+
+    let first_type = types.next()?;
+    let is_concrete = !first_type
+        .split(|character| !is_identifier_char(character))
+        .any(|identifier| type_parameters.contains(identifier));
+    (is_concrete && types.all(|typ| typ == first_type)).then_some(first_type)
+}
+
+/// Replaces the `type_parameters` in the rendered type `typ` with `concrete_type`.
+fn instantiate_type_parameters(
+    typ: &str,
+    type_parameters: &HashSet<&str>,
+    concrete_type: &str,
+) -> String {
+    let mut instantiated = String::with_capacity(typ.len());
+    let mut rest = typ;
+    while let Some(start) = rest.find(is_identifier_char) {
+        instantiated.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let end = rest.find(|character| !is_identifier_char(character)).unwrap_or(rest.len());
+        let identifier = &rest[..end];
+        if type_parameters.contains(identifier) {
+            instantiated.push_str(concrete_type);
+        } else {
+            instantiated.push_str(identifier);
+        }
+        rest = &rest[end..];
+    }
+    instantiated.push_str(rest);
+    instantiated
+}
+
+/// Returns true if the rendered type `typ` names a struct-like type, as opposed to a primitive
+/// type, a likely type parameter or a type that isn't named (like arrays or references).
+fn is_borrowable_type(typ: &str) -> bool {
+    let type_name = typ.split('<').next().unwrap_or(typ);
+    type_name.len() > 1
+        && type_name.starts_with(|character: char| character.is_ascii_uppercase())
+        && type_name.chars().all(is_identifier_char)
+        && PrimitiveType::lookup_by_name(type_name).is_none()
+}
+
+/// Adds `text` on a new line at the end of `item`'s detail.
+fn append_to_detail(item: &mut CompletionItem, text: String) {
+    item.detail = Some(match item.detail.take() {
+        Some(detail) => format!("{detail}\n{text}"),
+        None => text,
+    });
+}
+
+fn is_identifier_char(character: char) -> bool {
+    character.is_alphanumeric() || character == '_'
+}
+
+/// A suggestion adds nothing if its name is exactly the one already typed and the parameter
+/// being completed already has a type, as accepting it would leave the code unchanged
+/// (or repeat the type).
+fn suggestion_adds_nothing(param_name: &str, name: &str, current_typ: &UnresolvedType) -> bool {
+    param_name == name && !matches!(current_typ.typ, UnresolvedTypeData::Error)
+}
+
+/// Renders a parameter type for a completion label.
+///
+/// Named types are shown by their last path segment (so `std::option::Option<Field>` becomes
+/// `Option<Field>`) and closure environments are left out, which keeps labels short.
+fn render_param_type(typ: &UnresolvedType) -> String {
+    match &typ.typ {
+        UnresolvedTypeData::Named(path, args, _) if !path.segments.is_empty() => {
+            format!("{}{}", path.last_name(), render_generic_args(args))
+        }
+        UnresolvedTypeData::TraitAsType(path, args) if !path.segments.is_empty() => {
+            format!("impl {}{}", path.last_name(), render_generic_args(args))
+        }
+        UnresolvedTypeData::Array(length, typ) => format!("[{}; {length}]", render_param_type(typ)),
+        UnresolvedTypeData::Vector(typ) => format!("[{}]", render_param_type(typ)),
+        UnresolvedTypeData::Reference(typ, false) => format!("&{}", render_param_type(typ)),
+        UnresolvedTypeData::Reference(typ, true) => format!("&mut {}", render_param_type(typ)),
+        UnresolvedTypeData::Parenthesized(typ) => format!("({})", render_param_type(typ)),
+        UnresolvedTypeData::Tuple(types) => {
+            let types = vecmap(types, render_param_type);
+            if types.len() == 1 {
+                format!("({},)", types[0])
+            } else {
+                format!("({})", types.join(", "))
+            }
+        }
+        UnresolvedTypeData::Function(args, ret, _env, unconstrained) => {
+            let unconstrained = if *unconstrained { "unconstrained " } else { "" };
+            let args = vecmap(args, render_param_type).join(", ");
+            format!("{unconstrained}fn({args}) -> {}", render_param_type(ret))
+        }
+        _ => typ.to_string(),
+    }
+}
+
+fn render_generic_args(args: &GenericTypeArgs) -> String {
+    if args.is_empty() {
+        return String::new();
+    }
+
+    let ordered_args = args.ordered_args.iter().map(render_param_type);
+    let named_args =
+        args.named_args.iter().map(|(name, typ)| format!("{name} = {}", render_param_type(typ)));
+    format!("<{}>", ordered_args.chain(named_args).collect::<Vec<_>>().join(", "))
+}
+
+/// Returns names that are commonly given to parameters of the given type.
+fn parameter_names_for_type(
+    typ: &UnresolvedTypeData,
+    numeric_names: bool,
+) -> &'static [&'static str] {
+    match typ {
+        UnresolvedTypeData::Function(..) => &["f", "callback", "handler"],
+        UnresolvedTypeData::Named(path, args, _) if numeric_names && args.is_empty() => {
+            if path.segments.len() == 1 && is_integer_type_name(path.last_name()) {
+                &["len", "count", "index", "n"]
+            } else {
+                &[]
+            }
+        }
+        _ => &[],
+    }
+}
+
+/// Derives a parameter name from the name of a (non-primitive) named type, like `hello_world`
+/// (or `hw`, depending on `style`) for `HelloWorld`.
+fn parameter_name_from_type_name(
+    typ: &UnresolvedTypeData,
+    style: ParameterNameStyle,
+) -> Option<String> {
+    let UnresolvedTypeData::Named(path, _, _) = typ else {
+        return None;
+    };
+    let type_name = path.segments.last()?.ident.as_str();
+
+    // Skip primitive types and single-letter names, which are most likely type parameters
+    if PrimitiveType::lookup_by_name(type_name).is_some() || type_name.len() == 1 {
+        return None;
+    }
+
+    let snake_case_name = type_name.to_case(Case::Snake);
+    match style {
+        ParameterNameStyle::SnakeCase => Some(snake_case_name),
+        ParameterNameStyle::Abbreviated => {
+            Some(snake_case_name.split('_').filter_map(|part| part.chars().next()).collect())
+        }
+    }
+}
+
+/// Returns true for names like `u32` or `i8`.
+fn is_integer_type_name(name: &str) -> bool {
+    name.strip_prefix(['u', 'i']).is_some_and(|bit_size| bit_size.parse::<u32>().is_ok())
+}
+
 // Don't suggest names of parameters that already exist in the given function,
-// unless it's the name currently being completed.
+// unless it's the name currently being completed, nor names of the function's local variables
+// (a parameter with that name would be shadowed).
 fn names_to_exclude(function: &NoirFunction, name: &str) -> HashSet<String> {
     let mut names_to_exclude = HashSet::new();
     for parameter in function.parameters() {
@@ -164,5 +1376,10 @@ fn names_to_exclude(function: &NoirFunction, name: &str) -> HashSet<String> {
             names_to_exclude.insert(ident.to_string());
         }
     }
+
+    let mut collector = FreeIdentifierCollector::default();
+    function.def.body.accept(None, &mut collector);
+    names_to_exclude.extend(collector.bound);
+
     names_to_exclude
 }
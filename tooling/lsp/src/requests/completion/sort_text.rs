@@ -1,8 +1,24 @@
+/// When completing the first parameter of a method, `self` receivers are almost
+/// always what's wanted so they go before anything else.
+pub(super) fn self_receiver_sort_text() -> String {
+    "0".to_string()
+}
+
 /// Local variables should be suggested before anything else.
 pub(super) fn local_variable_sort_text() -> String {
     "a".to_string()
 }
 
+/// Parameter name suggestions are local-variable-like, but they are further ordered by a pair of
+/// ranks (lower goes first, both are zero if the parameter being completed has no type yet), and
+/// then by the order in which they were found.
+pub(super) fn ranked_parameter_sort_text(
+    (first, second): (usize, usize),
+    discovery_index: usize,
+) -> String {
+    format!("{}{first}{second}{discovery_index:05}", local_variable_sort_text())
+}
+
 /// Sort text for "new" methods: we want these to show up before anything else,
 /// if we are completing at something like `Foo::`
 pub(super) fn new_sort_text() -> String {
@@ -3,8 +3,11 @@ mod after_change;
 #[cfg(test)]
 mod completion_tests {
     use crate::{
+        LspState,
         notifications::on_did_open_text_document,
         requests::{
+            CancellationToken, CompletionsOptions, LspInitializationOptions, ParameterNameStyle,
+            ParameterRanking,
             completion::{
                 completion_items::{
                     completion_item_with_detail, completion_item_with_sort_text,
@@ -12,7 +15,10 @@ mod completion_tests {
                     simple_completion_item, snippet_completion_item,
                     trait_impl_method_completion_item,
                 },
-                sort_text::{auto_import_sort_text, self_mismatch_sort_text},
+                completion_response,
+                sort_text::{
+                    auto_import_sort_text, ranked_parameter_sort_text, self_mismatch_sort_text,
+                },
                 variable_completion_item,
             },
             on_completion_request,
@@ -23,22 +29,39 @@ mod completion_tests {
     };
 
     use async_lsp::lsp_types::{
-        CompletionItem, CompletionItemKind, CompletionItemLabelDetails, CompletionParams,
-        CompletionResponse, DidOpenTextDocumentParams, Documentation, PartialResultParams,
-        Position, TextDocumentIdentifier, TextDocumentItem, TextDocumentPositionParams,
-        WorkDoneProgressParams,
+        CompletionItem, CompletionItemKind, CompletionItemLabelDetails, CompletionItemTag,
+        CompletionParams, CompletionResponse, DidOpenTextDocumentParams, Documentation,
+        PartialResultParams, Position, TextDocumentIdentifier, TextDocumentItem,
+        TextDocumentPositionParams, Url, WorkDoneProgressParams,
     };
     use tokio::test;
 
     /// Given a string with ">|<" (cursor) in it, returns all completions that are available
     /// at that position together with the string with ">|<" removed.
     async fn get_completions(src: &str) -> (Vec<CompletionItem>, String) {
+        get_completions_with_options(src, LspInitializationOptions::default().completions).await
+    }
+
+    /// Like `get_completions`, but using the given completion options.
+    async fn get_completions_with_options(
+        src: &str,
+        options: CompletionsOptions,
+    ) -> (Vec<CompletionItem>, String) {
         let (mut state, noir_text_document) = test_utils::init_lsp_server("document_symbol").await;
+        state.options.completions = options;
+        get_completions_in_state(&mut state, noir_text_document, src).await
+    }
 
+    /// Like `get_completions`, but opening the document in an already initialized `state`.
+    async fn get_completions_in_state(
+        state: &mut LspState,
+        noir_text_document: Url,
+        src: &str,
+    ) -> (Vec<CompletionItem>, String) {
         let (line, column, src) = get_cursor_line_and_column(src);
 
         let _ = on_did_open_text_document(
-            &mut state,
+            state,
             DidOpenTextDocumentParams {
                 text_document: TextDocumentItem {
                     uri: noir_text_document.clone(),
@@ -49,24 +72,42 @@ mod completion_tests {
             },
         );
 
-        let response = on_completion_request(
-            &mut state,
-            CompletionParams {
-                text_document_position: TextDocumentPositionParams {
-                    text_document: TextDocumentIdentifier { uri: noir_text_document },
-                    position: Position { line: line as u32, character: column as u32 },
-                },
-                work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
-                partial_result_params: PartialResultParams { partial_result_token: None },
-                context: None,
+        let position = Position { line: line as u32, character: column as u32 };
+        let items = request_completions(state, noir_text_document, position).await;
+        (items, src)
+    }
+
+    /// Returns the completions at `position` in the document, whether it was opened or not.
+    async fn request_completions(
+        state: &mut LspState,
+        noir_text_document: Url,
+        position: Position,
+    ) -> Vec<CompletionItem> {
+        let response =
+            on_completion_request(state, completion_params(noir_text_document, position))
+                .await
+                .expect("Could not execute on_completion_request");
+
+        if let Some(CompletionResponse::Array(items)) = response { items } else { vec![] }
+    }
+
+    fn completion_params(noir_text_document: Url, position: Position) -> CompletionParams {
+        CompletionParams {
+            text_document_position: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: noir_text_document },
+                position,
             },
-        )
-        .await
-        .expect("Could not execute on_completion_request");
+            work_done_progress_params: WorkDoneProgressParams { work_done_token: None },
+            partial_result_params: PartialResultParams { partial_result_token: None },
+            context: None,
+        }
+    }
 
-        let items =
-            if let Some(CompletionResponse::Array(items)) = response { items } else { vec![] };
-        (items, src)
+    /// A `name: Type` suggestion for a parameter without a type, which was the
+    /// `discovery_index`-th parameter suggestion found.
+    fn parameter_completion_item(label: &str, discovery_index: usize) -> CompletionItem {
+        let sort_text = ranked_parameter_sort_text((0, 0), discovery_index);
+        completion_item_with_sort_text(variable_completion_item(label, None), sort_text)
     }
 
     fn assert_items_match(mut items: Vec<CompletionItem>, mut expected: Vec<CompletionItem>) {
@@ -3535,7 +3576,7 @@ fn main() {
         }
         "#;
 
-        assert_completion(src, vec![variable_completion_item("hello: HelloWorld", None)]).await;
+        assert_completion(src, vec![parameter_completion_item("hello: HelloWorld", 0)]).await;
     }
 
     #[test]
@@ -3560,7 +3601,7 @@ fn main() {
         fn four(help: Help) {}
         "#;
 
-        assert_completion(src, vec![variable_completion_item("hello: HelloWorld", None)]).await;
+        assert_completion(src, vec![parameter_completion_item("hello: HelloWorld", 0)]).await;
     }
 
     #[test]
@@ -3578,7 +3619,7 @@ fn main() {
         fn four(help: Help) {}
         "#;
 
-        assert_completion(src, vec![variable_completion_item("hello: HelloWorld", None)]).await;
+        assert_completion(src, vec![parameter_completion_item("hello: HelloWorld", 0)]).await;
     }
 
     #[test]
@@ -3594,6 +3635,652 @@ fn main() {
         fn four(help: Help) {}
         "#;
 
-        assert_completion(src, vec![variable_completion_item("hello: HelloWorld", None)]).await;
+        assert_completion(src, vec![parameter_completion_item("hello: HelloWorld", 0)]).await;
+    }
+
+    #[test]
+    async fn autocompletes_function_parameter_name_for_function_type() {
+        let src = r#"
+        fn one(ca>|<: fn(Field) -> Field) {}
+        "#;
+
+        assert_completion(src, vec![variable_completion_item("callback", None)]).await;
+    }
+
+    #[test]
+    async fn does_not_suggest_parameter_identical_to_the_one_being_typed() {
+        let src = r#"
+        fn one(callback>|<: fn(Field) -> Field) {}
+
+        fn two(callback: fn(Field) -> Field) {}
+        "#;
+
+        assert_completion(src, vec![]).await;
+    }
+
+    #[test]
+    async fn suggests_self_receivers_first_in_impl_method() {
+        let src = r#"
+        struct Foo {}
+
+        impl Foo {
+            fn one(se>|<) {}
+
+            fn two(seed: Field) {}
+        }
+        "#;
+
+        let (mut items, _) = get_completions(src).await;
+        items.sort_by_key(|item| (item.sort_text.clone(), item.label.clone()));
+
+        let labels = items.iter().map(|item| item.label.as_str()).collect::<Vec<_>>();
+        assert_eq!(labels, vec!["&mut self", "&self", "self", "seed: Field"]);
+    }
+
+    #[test]
+    async fn autocompletes_function_parameter_from_macro_generated_function() {
+        let src = r#"
+        #[make_deposit]
+        struct Foo {}
+
+        comptime fn make_deposit(_: TypeDefinition) -> Quoted {
+            quote { fn deposit(amount: Field) {} }
+        }
+
+        fn one(am>|<) {}
+        "#;
+
+        assert_completion(src, vec![parameter_completion_item("amount: Field", 0)]).await;
+    }
+
+    #[test]
+    async fn autocompletes_function_parameter_with_short_type_label() {
+        let src = r#"
+        fn one(ma>|<) {}
+
+        fn two(maybe_values: std::option::Option<std::collections::bounded_vec::BoundedVec<Field, 3>>) {}
+        "#;
+
+        assert_completion(
+            src,
+            vec![parameter_completion_item("maybe_values: Option<BoundedVec<Field, 3>>", 0)],
+        )
+        .await;
+    }
+
+    #[test]
+    async fn ranks_function_parameters_by_type_or_name_depending_on_policy() {
+        let src = r#"
+        fn one(amo>|<: Field) {}
+
+        fn two(amount_in_wei: u64) {}
+
+        fn three(max_amount: Field) {}
+        "#;
+
+        for (parameter_ranking, expected) in [
+            (ParameterRanking::TypeFirst, ["max_amount: Field", "amount_in_wei: u64"]),
+            (ParameterRanking::NameFirst, ["amount_in_wei: u64", "max_amount: Field"]),
+        ] {
+            let options = CompletionsOptions {
+                parameter_ranking,
+                ..LspInitializationOptions::default().completions
+            };
+            let (mut items, _) = get_completions_with_options(src, options).await;
+            items.sort_by_key(|item| item.sort_text.clone());
+
+            let labels = items.iter().map(|item| item.label.as_str()).collect::<Vec<_>>();
+            assert_eq!(labels, expected);
+        }
+    }
+
+    #[test]
+    async fn autocompletes_idiomatic_parameter_names_for_integer_type() {
+        let src = r#"
+        fn one(c>|<: u32) {}
+        "#;
+
+        assert_completion(src, vec![variable_completion_item("count", None)]).await;
+
+        let options = CompletionsOptions {
+            numeric_parameter_names: false,
+            ..LspInitializationOptions::default().completions
+        };
+        let (items, _) = get_completions_with_options(src, options).await;
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    async fn does_not_suggest_idiomatic_parameter_name_that_already_exists() {
+        let src = r#"
+        fn one(count: u32, c>|<: u32) {}
+        "#;
+
+        assert_completion(src, vec![]).await;
+    }
+
+    #[test]
+    async fn autocompletes_function_parameter_type_after_colon() {
+        let src = r#"
+        fn one(x:>|<) {}
+
+        fn two(x: Field) {}
+
+        fn three(x: u64, y: i8) {}
+        "#;
+
+        assert_completion(
+            src,
+            vec![
+                simple_completion_item("Field", CompletionItemKind::STRUCT, None),
+                simple_completion_item("u64", CompletionItemKind::STRUCT, None),
+            ],
+        )
+        .await;
+    }
+
+    #[test]
+    async fn autocompletes_parameter_name_derived_from_type_name_in_configured_style() {
+        let src = r#"
+        struct HelloWorld {}
+
+        fn one(h>|<: HelloWorld) {}
+        "#;
+
+        for (parameter_name_style, expected) in [
+            (ParameterNameStyle::SnakeCase, "hello_world"),
+            (ParameterNameStyle::Abbreviated, "hw"),
+        ] {
+            let options = CompletionsOptions {
+                parameter_name_style,
+                ..LspInitializationOptions::default().completions
+            };
+            let (items, _) = get_completions_with_options(src, options).await;
+            assert_items_match(items, vec![variable_completion_item(expected, None)]);
+        }
+    }
+
+    #[test]
+    async fn autocompletes_parameter_name_following_other_parameters_naming_convention() {
+        let options = CompletionsOptions {
+            parameter_name_style: ParameterNameStyle::Abbreviated,
+            ..LspInitializationOptions::default().completions
+        };
+
+        for (src, expected) in [
+            ("struct Config {}\nfn one(in_a: Field, in_b: Field, in_>|<: Config) {}", "in_c"),
+            ("struct Config {}\nfn one(a_out: Field, b_out: Field, c>|<: Config) {}", "c_out"),
+        ] {
+            let (items, _) = get_completions_with_options(src, options).await;
+            assert_items_match(items, vec![variable_completion_item(expected, None)]);
+        }
+    }
+
+    #[test]
+    async fn autocompletes_function_parameter_in_borrowed_form_if_others_are_borrowed() {
+        let src = r#"
+        struct Config {}
+
+        fn one(con>|<) {}
+
+        fn two(config: Config) {}
+        fn three(settings: &Config) {}
+        "#;
+
+        assert_completion(
+            src,
+            vec![
+                parameter_completion_item("config: Config", 0),
+                parameter_completion_item("config: &Config", 1),
+            ],
+        )
+        .await;
+    }
+
+    #[test]
+    async fn autocompletes_function_parameter_used_in_body() {
+        let src = r#"
+        global ALPHA: Field = 2;
+
+        fn one(a>|<) -> Field {
+            let doubled = amount * ALPHA;
+            doubled + another
+        }
+        "#;
+
+        assert_completion(
+            src,
+            vec![
+                variable_completion_item("amount", None),
+                variable_completion_item("another", None),
+            ],
+        )
+        .await;
+    }
+
+    #[test]
+    async fn does_not_suggest_functions_called_in_body_as_parameters() {
+        let src = r#"
+        fn one(a>|<) {
+            println(amount);
+            assert_total(another);
+        }
+        "#;
+
+        assert_completion(
+            src,
+            vec![
+                variable_completion_item("amount", None),
+                variable_completion_item("another", None),
+            ],
+        )
+        .await;
+    }
+
+    #[test]
+    async fn does_not_suggest_imported_items_used_in_body_as_parameters() {
+        let src = r#"
+        mod fees {
+            pub global ALLOWANCE: Field = 2;
+
+            pub fn apply_fee(value: Field) -> Field {
+                value
+            }
+        }
+
+        use fees::{ALLOWANCE, apply_fee};
+
+        fn one(a>|<) -> Field {
+            let fee = apply_fee;
+            fee(amount) + ALLOWANCE
+        }
+        "#;
+
+        assert_completion(src, vec![variable_completion_item("amount", None)]).await;
+    }
+
+    #[test]
+    async fn autocompletes_all_remaining_function_parameters_at_once() {
+        let src = r#"
+        fn one(a: Field, b>|<) {}
+
+        fn two(a: Field, b: u32, c: bool) {}
+        "#;
+
+        assert_completion(
+            src,
+            vec![
+                parameter_completion_item("b: u32", 0),
+                snippet_completion_item(
+                    "b: u32, c: bool",
+                    CompletionItemKind::SNIPPET,
+                    "${1:b}: u32, ${2:c}: bool",
+                    Some("fill remaining parameters".to_string()),
+                ),
+            ],
+        )
+        .await;
+    }
+
+    #[test]
+    async fn does_not_suggest_parameter_named_like_local_variable() {
+        let src = r#"
+        fn one(t>|<) {
+            let total = 1;
+        }
+
+        fn two(total: Field, token: u32) {}
+        "#;
+
+        assert_completion(src, vec![parameter_completion_item("token: u32", 0)]).await;
+    }
+
+    #[test]
+    async fn returns_partial_parameter_suggestions_when_search_is_exhausted() {
+        let src = r#"
+        fn one(a>|<) {}
+
+        fn two(amount: Field) {}
+
+        fn three(another: Field) {}
+        "#;
+
+        // `one` and `two` are looked at, `three` isn't anymore
+        let options = CompletionsOptions {
+            max_parameter_search_functions: 2,
+            ..LspInitializationOptions::default().completions
+        };
+        let (items, _) = get_completions_with_options(src, options).await;
+        assert_items_match(items, vec![parameter_completion_item("amount: Field", 0)]);
+
+        let options = CompletionsOptions {
+            max_parameter_search_functions: 0,
+            ..LspInitializationOptions::default().completions
+        };
+        let (items, _) = get_completions_with_options(src, options).await;
+        assert_items_match(items, vec![]);
+    }
+
+    #[test]
+    async fn returns_partial_parameter_suggestions_when_request_is_cancelled() {
+        let src = r#"
+        fn one(c>|<: u32) {}
+
+        fn two(count_limit: u32) {}
+        "#;
+
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("document_symbol").await;
+        let (line, column, text) = get_cursor_line_and_column(src);
+        let _ = on_did_open_text_document(
+            &mut state,
+            DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: noir_text_document.clone(),
+                    language_id: "noir".to_string(),
+                    version: 0,
+                    text,
+                },
+            },
+        );
+        let position = Position { line: line as u32, character: column as u32 };
+
+        // Names that don't need searching other functions are still suggested
+        let cancellation = CancellationToken::default();
+        cancellation.cancel();
+        let params = completion_params(noir_text_document.clone(), position);
+        let response = completion_response(&mut state, params, cancellation)
+            .expect("Could not execute completion_response");
+        let Some(CompletionResponse::Array(items)) = response else {
+            panic!("Expected completion items");
+        };
+        assert_items_match(items, vec![variable_completion_item("count", None)]);
+
+        let items = request_completions(&mut state, noir_text_document, position).await;
+        let mut labels = items.iter().map(|item| item.label.as_str()).collect::<Vec<_>>();
+        labels.sort();
+        assert_eq!(labels, vec!["count", "count_limit: u32"]);
+    }
+
+    #[test]
+    async fn marks_parameter_suggestion_conflicting_with_body_usage() {
+        let src = r#"
+        fn one(a>|<) {
+            let x: u32 = amount;
+        }
+
+        fn two(amount: Field) {}
+
+        fn three(another: u32) {}
+        "#;
+
+        let risky = completion_item_with_detail(
+            parameter_completion_item("amount: Field", 0),
+            "`amount` is used as `u32` in the function body".to_string(),
+        );
+        let risky = CompletionItem { tags: Some(vec![CompletionItemTag::DEPRECATED]), ..risky };
+
+        assert_completion(src, vec![risky, parameter_completion_item("another: u32", 1)]).await;
+    }
+
+    #[test]
+    async fn keeps_full_type_of_truncated_parameter_suggestion_conflicting_with_body_usage() {
+        let src = r#"
+        fn one(a>|<) {
+            let x: u32 = amount;
+        }
+
+        fn two(amount: Field) {}
+        "#;
+
+        let options = CompletionsOptions {
+            max_parameter_type_length: 3,
+            ..LspInitializationOptions::default().completions
+        };
+        let (items, _) = get_completions_with_options(src, options).await;
+
+        let risky = CompletionItem {
+            insert_text: Some("amount: Field".to_string()),
+            tags: Some(vec![CompletionItemTag::DEPRECATED]),
+            ..completion_item_with_detail(
+                parameter_completion_item("amount: Fie…", 0),
+                "Field\n`amount` is used as `u32` in the function body".to_string(),
+            )
+        };
+        assert_items_match(items, vec![risky]);
+    }
+
+    #[test]
+    async fn autocompletes_parameter_names_mentioned_in_doc_comments() {
+        let src = r#"
+        /// Deposits funds.
+        ///
+        /// @param amount the value to deposit
+        /// - `max_amount`: the most that can be deposited
+        /// Note: this is an example
+        fn one(a>|<) {}
+        "#;
+
+        assert_completion(
+            src,
+            vec![
+                variable_completion_item("amount", None),
+                variable_completion_item("max_amount", None),
+            ],
+        )
+        .await;
+    }
+
+    #[test]
+    async fn autocompletes_function_parameter_type_after_non_ascii_parameter_name() {
+        let src = r#"
+        fn one(x²:>|<) {}
+
+        fn two(x²: Field) {}
+        "#;
+
+        assert_completion(
+            src,
+            vec![simple_completion_item("Field", CompletionItemKind::STRUCT, None)],
+        )
+        .await;
+    }
+
+    #[test]
+    async fn autocompletes_underscore_prefixed_name_for_unused_parameter() {
+        let src = r#"
+        fn one(amount>|<: Field) -> Field {
+            1
+        }
+
+        fn two(amount: Field) -> Field {
+            amount
+        }
+        "#;
+
+        assert_completion(src, vec![variable_completion_item("_amount", None)]).await;
+    }
+
+    #[test]
+    async fn orders_equally_ranked_function_parameters_by_source_order() {
+        let src = r#"
+        fn one(a>|<: Field) {}
+
+        fn two(avocado: Field) {}
+
+        fn three(apple: Field) {}
+
+        fn four(apricot: Field) {}
+        "#;
+
+        for _ in 0..3 {
+            let (mut items, _) = get_completions(src).await;
+            items.sort_by_key(|item| item.sort_text.clone());
+
+            let labels = items.iter().map(|item| item.label.as_str()).collect::<Vec<_>>();
+            assert_eq!(labels, vec!["avocado: Field", "apple: Field", "apricot: Field"]);
+        }
+    }
+
+    #[test]
+    async fn orders_untyped_function_parameters_by_source_order() {
+        let src = r#"
+        fn one(a>|<) {}
+
+        fn two(avocado: Field) {}
+
+        fn three(apple: u32) {}
+
+        fn four(apricot: bool) {}
+        "#;
+
+        let (mut items, _) = get_completions(src).await;
+        items.sort_by_key(|item| item.sort_text.clone());
+
+        let labels = items.iter().map(|item| item.label.as_str()).collect::<Vec<_>>();
+        assert_eq!(labels, vec!["avocado: Field", "apple: u32", "apricot: bool"]);
+    }
+
+    #[test]
+    async fn notes_parameter_suggestion_going_over_max_parameters() {
+        let src = r#"
+        fn one(a: u32, b: u32, c>|<) {}
+
+        fn two(count: u64) {}
+        "#;
+
+        let options = CompletionsOptions {
+            max_parameters: 2,
+            ..LspInitializationOptions::default().completions
+        };
+        let (items, _) = get_completions_with_options(src, options).await;
+        let note = "More than 2 parameters, consider grouping them into a struct";
+        let expected = completion_item_with_detail(
+            parameter_completion_item("count: u64", 0),
+            note.to_string(),
+        );
+        assert_eq!(items, vec![expected]);
+    }
+
+    #[test]
+    async fn truncates_long_parameter_type_in_label() {
+        let src = r#"
+        fn one(e>|<) {}
+
+        fn two(entries: Map<Field, (u64, bool, [u8; 32])>) {}
+        "#;
+
+        let options = CompletionsOptions {
+            max_parameter_type_length: 10,
+            ..LspInitializationOptions::default().completions
+        };
+        let (items, _) = get_completions_with_options(src, options).await;
+        assert_eq!(items.len(), 1);
+
+        let item = &items[0];
+        assert_eq!(item.label, "entries: Map<Field,…");
+        assert_eq!(
+            item.insert_text,
+            Some("entries: Map<Field, (u64, bool, [u8; 32])>".to_string())
+        );
+        assert_eq!(item.detail, Some("Map<Field, (u64, bool, [u8; 32])>".to_string()));
+    }
+
+    #[test]
+    async fn recomputes_cached_function_parameters_when_document_changes() {
+        let src = r#"
+        fn one(a>|<) {}
+
+        fn two(amount: Field) {}
+        "#;
+
+        let (mut state, noir_text_document) = test_utils::init_lsp_server("document_symbol").await;
+        let expected = vec![parameter_completion_item("amount: Field", 0)];
+
+        for _ in 0..2 {
+            let (items, _) =
+                get_completions_in_state(&mut state, noir_text_document.clone(), src).await;
+            assert_eq!(items, expected);
+        }
+
+        let src = src.replace("amount: Field", "amount: u64");
+        let (items, _) = get_completions_in_state(&mut state, noir_text_document, &src).await;
+        assert_eq!(items, vec![parameter_completion_item("amount: u64", 0)]);
+    }
+
+    #[test]
+    async fn autocompletes_function_parameter_in_document_that_was_never_opened() {
+        let (mut state, noir_text_document) =
+            test_utils::init_lsp_server("parameter_completion").await;
+        let expected = vec![parameter_completion_item("amount: Field", 0)];
+
+        // After the `a` in `fn one(a: Field) {}`
+        let position = Position { line: 2, character: 8 };
+        for _ in 0..2 {
+            let items = request_completions(&mut state, noir_text_document.clone(), position).await;
+            assert_eq!(items, expected);
+        }
+    }
+
+    #[test]
+    async fn hints_generic_parameter_type_instantiated_with_concrete_type() {
+        let src = r#"
+        fn one(x: Field, i>|<) {}
+
+        fn two<T>(items: Vec<T>) {}
+        "#;
+
+        let options = CompletionsOptions {
+            generic_instantiation_hints: true,
+            ..LspInitializationOptions::default().completions
+        };
+        let (items, _) = get_completions_with_options(src, options).await;
+
+        let hint = completion_item_with_detail(
+            variable_completion_item("items: Vec<Field>", Some("hint".to_string())),
+            "Hint: `Vec<T>` with its type parameters set to `Field`".to_string(),
+        );
+        assert_items_match(items, vec![parameter_completion_item("items: Vec<T>", 0), hint]);
+    }
+
+    #[test]
+    async fn suggests_trait_default_and_impl_override_parameter_once() {
+        let src = r#"
+        trait Account {
+            fn deposit(self, amount: Field) {}
+
+            fn withdraw(self, amount: Field);
+        }
+
+        struct Bank {}
+
+        impl Account for Bank {
+            fn deposit(self, amount: Field) {}
+
+            fn withdraw(self, a>|<) {}
+        }
+        "#;
+
+        assert_completion(src, vec![parameter_completion_item("amount: Field", 0)]).await;
+    }
+
+    #[test]
+    async fn suggests_trait_default_method_parameter_in_impl_of_trait_in_other_module() {
+        let src = r#"
+        mod bank {
+            pub trait Account {
+                fn deposit(self, amount: Field) {}
+
+                fn withdraw(self, other: u32);
+            }
+        }
+
+        struct Bank {}
+
+        impl bank::Account for Bank {
+            fn withdraw(self, a>|<) {}
+        }
+        "#;
+
+        assert_completion(src, vec![parameter_completion_item("amount: Field", 0)]).await;
     }
 }
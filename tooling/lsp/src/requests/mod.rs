@@ -1,6 +1,8 @@
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::{collections::HashMap, future::Future};
 
 use crate::notifications::fake_stdlib_workspace;
@@ -39,6 +41,7 @@ use crate::{
     types::{InitializeResult, NargoCapability, NargoTestsOptions, ServerCapabilities},
 };
 
+pub(crate) use completion::ParameterCandidatesCache;
 pub(crate) use workspace_symbol::WorkspaceSymbolCache;
 
 // Handlers
@@ -116,6 +119,9 @@ pub(crate) struct LspInitializationOptions {
 
     #[serde(rename = "inlayHints", default = "default_inlay_hints")]
     pub(crate) inlay_hints: InlayHintsOptions,
+
+    #[serde(rename = "completions", default = "default_completions")]
+    pub(crate) completions: CompletionsOptions,
 }
 
 #[derive(Debug, Deserialize, Serialize, Copy, Clone)]
@@ -160,6 +166,64 @@ pub(crate) struct ChainingHintsOptions {
     pub(crate) enabled: bool,
 }
 
+#[derive(Debug, Deserialize, Serialize, Copy, Clone)]
+pub(crate) struct CompletionsOptions {
+    /// How parameter name suggestions are ranked when the parameter being completed already has a type.
+    #[serde(rename = "parameterRanking", default = "default_parameter_ranking")]
+    pub(crate) parameter_ranking: ParameterRanking,
+
+    /// Whether idiomatic names like `len` or `index` are suggested for parameters of integer types.
+    #[serde(rename = "numericParameterNames", default = "default_numeric_parameter_names")]
+    pub(crate) numeric_parameter_names: bool,
+
+    /// How parameter names derived from a type name (like `hello_world` for `HelloWorld`) are built.
+    #[serde(rename = "parameterNameStyle", default = "default_parameter_name_style")]
+    pub(crate) parameter_name_style: ParameterNameStyle,
+
+    /// How many functions each pass of the search for parameters to suggest looks at before
+    /// giving up and returning the suggestions found so far.
+    #[serde(
+        rename = "maxParameterSearchFunctions",
+        default = "default_max_parameter_search_functions"
+    )]
+    pub(crate) max_parameter_search_functions: usize,
+
+    /// How many parameters a function can have before parameter suggestions that would add
+    /// another one get a note recommending to group parameters into a struct.
+    #[serde(rename = "maxParameters", default = "default_max_parameters")]
+    pub(crate) max_parameters: usize,
+
+    /// How many characters of a parameter's type are shown in the label of a parameter suggestion.
+    /// Longer types are cut off with an ellipsis, and shown in full in the suggestion's detail.
+    #[serde(rename = "maxParameterTypeLength", default = "default_max_parameter_type_length")]
+    pub(crate) max_parameter_type_length: usize,
+
+    /// Whether to also suggest parameters whose type uses type parameters the current function
+    /// doesn't have with those replaced by the concrete type of the function's other parameters.
+    #[serde(rename = "genericInstantiationHints", default = "default_generic_instantiation_hints")]
+    pub(crate) generic_instantiation_hints: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum ParameterRanking {
+    /// Suggestions with the same type go first, then the ones whose name matches better.
+    #[serde(rename = "typeFirst")]
+    TypeFirst,
+    /// Suggestions whose name matches better go first, then the ones with the same type.
+    #[serde(rename = "nameFirst")]
+    NameFirst,
+}
+
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum ParameterNameStyle {
+    /// The type name in snake case, for example `hello_world` for `HelloWorld`.
+    #[serde(rename = "snakeCase")]
+    SnakeCase,
+    /// The initials of the type name, for example `hw` for `HelloWorld`.
+    #[serde(rename = "abbreviated")]
+    Abbreviated,
+}
+
 fn default_enable_code_lens() -> bool {
     true
 }
@@ -201,6 +265,46 @@ fn default_inlay_hints() -> InlayHintsOptions {
     }
 }
 
+fn default_completions() -> CompletionsOptions {
+    CompletionsOptions {
+        parameter_ranking: default_parameter_ranking(),
+        numeric_parameter_names: default_numeric_parameter_names(),
+        parameter_name_style: default_parameter_name_style(),
+        max_parameter_search_functions: default_max_parameter_search_functions(),
+        max_parameters: default_max_parameters(),
+        max_parameter_type_length: default_max_parameter_type_length(),
+        generic_instantiation_hints: default_generic_instantiation_hints(),
+    }
+}
+
+fn default_parameter_ranking() -> ParameterRanking {
+    ParameterRanking::TypeFirst
+}
+
+fn default_numeric_parameter_names() -> bool {
+    true
+}
+
+fn default_parameter_name_style() -> ParameterNameStyle {
+    ParameterNameStyle::SnakeCase
+}
+
+fn default_max_parameter_search_functions() -> usize {
+    1000
+}
+
+fn default_max_parameters() -> usize {
+    7
+}
+
+fn default_max_parameter_type_length() -> usize {
+    40
+}
+
+fn default_generic_instantiation_hints() -> bool {
+    false
+}
+
 fn default_type_hints() -> TypeHintsOptions {
     TypeHintsOptions { enabled: default_type_hints_enabled() }
 }
@@ -246,6 +350,7 @@ impl Default for LspInitializationOptions {
             enable_code_lens: default_enable_code_lens(),
             enable_parsing_cache: default_enable_parsing_cache(),
             inlay_hints: default_inlay_hints(),
+            completions: default_completions(),
             enable_inlay_hints: default_enable_inlay_hints(),
             enable_completions: default_enable_completions(),
             enable_signature_help: default_enable_signature_help(),
@@ -571,6 +676,20 @@ pub(crate) fn on_shutdown(
     async { Ok(()) }
 }
 
+/// Lets the client stop a long running request, which then returns the results found so far.
+#[derive(Clone, Default)]
+pub(crate) struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub(crate) fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
 pub(crate) struct ProcessRequestCallbackArgs<'a> {
     pub(crate) location: noirc_errors::Location,
     pub(crate) workspace: &'a Workspace,
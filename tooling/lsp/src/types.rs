@@ -9,10 +9,11 @@ use serde::{Deserialize, Serialize};
 
 // Re-providing lsp_types that we don't need to override
 pub(crate) use async_lsp::lsp_types::{
-    CodeLens, CodeLensOptions, CodeLensParams, Command, Diagnostic, DiagnosticSeverity,
-    DidChangeConfigurationParams, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
-    DidOpenTextDocumentParams, DidSaveTextDocumentParams, InitializeParams, InitializedParams,
-    Position, PublishDiagnosticsParams, Range, ServerInfo, TextDocumentSyncCapability, Url,
+    CancelParams, CodeLens, CodeLensOptions, CodeLensParams, Command, Diagnostic,
+    DiagnosticSeverity, DidChangeConfigurationParams, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams,
+    InitializeParams, InitializedParams, Position, PublishDiagnosticsParams, Range, ServerInfo,
+    TextDocumentSyncCapability, Url,
 };
 
 pub(crate) mod request {
@@ -81,8 +82,8 @@ pub(crate) mod notification {
 
     // Re-providing lsp_types that we don't need to override
     pub(crate) use async_lsp::lsp_types::notification::{
-        DidChangeConfiguration, DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
-        DidSaveTextDocument, Exit, Initialized,
+        Cancel, DidChangeConfiguration, DidChangeTextDocument, DidCloseTextDocument,
+        DidOpenTextDocument, DidSaveTextDocument, Exit, Initialized,
     };
 
     pub(crate) struct NargoUpdateTests;
@@ -56,14 +56,17 @@ pub(crate) fn character_to_line_offset(line: &str, character: u32) -> Option<usi
 
 /// Given a string with a single ">|<" (cursor) in it, returns:
 /// 1. The line where the cursor is (zero-based)
-/// 2. The column where the cursor is (zero-based)
+/// 2. The column where the cursor is (zero-based, in UTF-16 code units like LSP positions)
 /// 3. that string with ">|<" removed
 #[cfg(test)]
 pub(crate) fn get_cursor_line_and_column(src: &str) -> (usize, usize, String) {
     let (line, column) = src
         .lines()
         .enumerate()
-        .find_map(|(line_index, line)| line.find(">|<").map(|char_index| (line_index, char_index)))
+        .find_map(|(line_index, line)| {
+            let byte_index = line.find(">|<")?;
+            Some((line_index, line[..byte_index].encode_utf16().count()))
+        })
         .expect("Expected to find one >|< in the source code");
 
     let src = src.replace(">|<", "");